@@ -0,0 +1,416 @@
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+
+/*!
+# Jargon Derive
+
+The `#[derive(Jargon)]` macro of [jargon-args](https://crates.io/crates/jargon-args), enabled
+with its `derive` feature. See the `FromJargon` trait there for the attributes it reads.
+*/
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as Tokens;
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, GenericArgument, Ident,
+    Lit, LitChar, LitStr, Meta, PathArguments, Type,
+};
+
+/// Implements `jargon_args::FromJargon` for a struct with named fields.
+#[proc_macro_derive(Jargon, attributes(jargon))]
+pub fn derive_jargon(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// What a field's type says about the argument.
+enum Kind {
+    Flag,
+    Optional(Type),
+    Multiple(Type),
+    Required(Type),
+}
+
+/// One field and the argument it declares.
+struct Field {
+    ident: Ident,
+    ty: Type,
+    kind: Kind,
+    short: Option<char>,
+    long: Option<String>,
+    default: Option<String>,
+    env: Option<String>,
+    value: String,
+    help: String,
+    count: bool,
+    positional: bool,
+    /// Whether the field asks for help, with `#[jargon(help)]`.
+    asks_help: bool,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<Tokens> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(input, "expected named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(input, "expected a struct")),
+    };
+
+    let mut name: Option<String> = None;
+    let mut about: Option<String> = doc(&input.attrs);
+    let mut usage: Vec<String> = Vec::new();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("jargon")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("about") {
+                about = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("usage") {
+                usage.push(meta.value()?.parse::<LitStr>()?.value());
+            } else {
+                return Err(meta.error("expected `name`, `about` or `usage`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let fields: Vec<Field> = fields.iter().map(field).collect::<syn::Result<_>>()?;
+
+    if usage.is_empty() && fields.iter().any(|f| f.positional) {
+        let mut line: String = String::from("[OPTION]...");
+        for f in fields.iter().filter(|f| f.positional) {
+            line.push(' ');
+            line.push_str(&match f.kind {
+                Kind::Required(_) => f.value.clone(),
+                Kind::Optional(_) | Kind::Flag => format!("[{}]", f.value),
+                Kind::Multiple(_) => format!("[{}]...", f.value),
+            });
+        }
+        usage.push(line);
+    }
+
+    let name: Tokens = match name {
+        Some(name) => quote!(#name),
+        None => quote!(::std::env!("CARGO_PKG_NAME")),
+    };
+    let about: Option<Tokens> = about.map(|about| quote!(.about(#about)));
+    let args: Vec<Tokens> = fields.iter().filter(|f| !f.positional).map(arg).collect();
+    let helps: Vec<String> = fields
+        .iter()
+        .filter(|f| f.asks_help)
+        .map(|f| key(f).1)
+        .collect();
+    let help_idents: Vec<&Ident> = fields
+        .iter()
+        .filter(|f| f.asks_help)
+        .map(|f| &f.ident)
+        .collect();
+    let values: Vec<Tokens> = fields.iter().map(value).collect::<syn::Result<_>>()?;
+    let idents: Vec<&Ident> = fields.iter().map(|f| &f.ident).collect();
+
+    let ident: &Ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::jargon_args::FromJargon for #ident #ty_generics #where_clause {
+            fn spec() -> ::jargon_args::Spec {
+                ::jargon_args::Spec::new(#name)
+                    #(.usage(#usage))*
+                    #about
+                    #(.arg(#args))*
+            }
+
+            fn from_jargon(
+                mut jargon: ::jargon_args::Jargon,
+            ) -> ::std::result::Result<Self, ::jargon_args::Error> {
+                let matches: ::jargon_args::Matches =
+                    <Self as ::jargon_args::FromJargon>::spec().parse(&mut jargon)?;
+                #(
+                    if matches.contains(#helps) {
+                        return ::std::result::Result::Err(::jargon_args::Error::Help(
+                            <Self as ::jargon_args::FromJargon>::help(),
+                        ));
+                    }
+                )*
+                let mut rest: ::std::collections::VecDeque<::std::string::String> =
+                    jargon.finish_strict()?.into();
+
+                #(let #idents = #values;)*
+
+                if !rest.is_empty() {
+                    return ::std::result::Result::Err(::jargon_args::Error::UnknownArgs(
+                        rest.into_iter()
+                            .map(|arg| ::jargon_args::Unknown {
+                                arg,
+                                suggestion: ::std::option::Option::None,
+                            })
+                            .collect(),
+                    ));
+                }
+
+                let value: Self = Self { #(#idents),* };
+                // Help fields are never true once built, so they are read here to keep them
+                // from being reported as unused.
+                #(let _ = &value.#help_idents;)*
+                ::std::result::Result::Ok(value)
+            }
+        }
+    })
+}
+
+/// Reads a field's type and attributes.
+fn field(field: &syn::Field) -> syn::Result<Field> {
+    let ident: Ident = field.ident.clone().expect("named field");
+    let name: String = ident.to_string().trim_start_matches("r#").to_string();
+
+    let mut f: Field = Field {
+        ident,
+        ty: field.ty.clone(),
+        kind: kind(&field.ty),
+        short: None,
+        long: None,
+        default: None,
+        env: None,
+        value: name.to_uppercase(),
+        help: doc(&field.attrs).unwrap_or_default(),
+        count: false,
+        positional: false,
+        asks_help: false,
+    };
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("jargon")) {
+        attr.parse_nested_meta(|meta| {
+            let given: bool = meta.input.peek(syn::Token![=]);
+            if meta.path.is_ident("short") {
+                f.short = Some(if given {
+                    meta.value()?.parse::<LitChar>()?.value()
+                } else {
+                    name.chars().next().unwrap_or('_')
+                });
+            } else if meta.path.is_ident("long") {
+                f.long = Some(if given {
+                    meta.value()?.parse::<LitStr>()?.value()
+                } else {
+                    name.replace('_', "-")
+                });
+            } else if meta.path.is_ident("default") {
+                f.default = Some(literal(&meta.value()?.parse::<Lit>()?)?);
+            } else if meta.path.is_ident("env") {
+                f.env = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("value") {
+                f.value = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("help") && given {
+                f.help = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("help") {
+                f.asks_help = true;
+            } else if meta.path.is_ident("count") {
+                f.count = true;
+            } else if meta.path.is_ident("positional") {
+                f.positional = true;
+            } else {
+                return Err(meta.error(
+                    "expected `short`, `long`, `default`, `env`, `value`, `help`, `count` or \
+                     `positional`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+
+    if f.positional && (f.short.is_some() || f.long.is_some() || f.count) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "positional fields take no `short`, `long` or `count`",
+        ));
+    }
+    if f.asks_help && (f.positional || f.count || !matches!(f.kind, Kind::Flag)) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "`help` fields must be `bool` flags",
+        ));
+    }
+    if f.short.is_none() && f.long.is_none() {
+        f.long = Some(name.replace('_', "-"));
+    }
+
+    Ok(f)
+}
+
+/// Tells what kind of argument a type is, by its last path segment.
+fn kind(ty: &Type) -> Kind {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last(),
+        _ => None,
+    };
+    let segment = match segment {
+        Some(segment) => segment,
+        None => return Kind::Required(ty.clone()),
+    };
+
+    let inner: Option<Type> = match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some(inner.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    match (segment.ident.to_string().as_str(), inner) {
+        ("bool", None) => Kind::Flag,
+        ("Option", Some(inner)) => Kind::Optional(inner),
+        ("Vec", Some(inner)) => Kind::Multiple(inner),
+        _ => Kind::Required(ty.clone()),
+    }
+}
+
+/// Joins the lines of doc comments, or None if there are none.
+fn doc(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) if nv.path.is_ident("doc") => match &nv.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" ").trim().to_string())
+    }
+}
+
+/// Writes a literal default value as the text it would be given as.
+fn literal(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(s) => Ok(s.value()),
+        Lit::Char(c) => Ok(c.value().to_string()),
+        Lit::Int(i) => Ok(i.base10_digits().to_string()),
+        Lit::Float(f) => Ok(f.base10_digits().to_string()),
+        Lit::Bool(b) => Ok(b.value.to_string()),
+        lit => Err(syn::Error::new_spanned(
+            lit,
+            "expected a literal default value",
+        )),
+    }
+}
+
+/// Returns the key of a field, and the spelling used to look it up in `matches`.
+fn key(f: &Field) -> (Tokens, String) {
+    let short: Option<String> = f.short.map(|c| format!("-{}", c));
+    let long: Option<String> = f.long.as_ref().map(|l| format!("--{}", l));
+
+    match (short, long) {
+        (Some(short), Some(long)) => (quote!([#short, #long]), long),
+        (Some(key), None) | (None, Some(key)) => (quote!(#key), key),
+        (None, None) => unreachable!("fields without keys get a long one"),
+    }
+}
+
+/// Renders the `Arg` declaring a field.
+fn arg(f: &Field) -> Tokens {
+    let (key, _) = key(f);
+    let help: &str = &f.help;
+    let value: &str = &f.value;
+
+    let mut arg: Tokens = match (&f.kind, f.count) {
+        (Kind::Flag, _) | (_, true) => quote!(::jargon_args::Arg::flag(#key)),
+        _ => quote!(::jargon_args::Arg::option(#key, #value)),
+    };
+    arg.extend(quote!(.help(#help)));
+    if let Some(default) = &f.default {
+        arg.extend(quote!(.default(#default)));
+    }
+    if let Some(env) = &f.env {
+        arg.extend(quote!(.env(#env)));
+    }
+    arg
+}
+
+/// Renders the expression taking a field's value out of `matches` or `rest`.
+fn value(f: &Field) -> syn::Result<Tokens> {
+    if f.positional {
+        return positional(f);
+    }
+
+    let (_, key) = key(f);
+    let ty: &Type = &f.ty;
+    let missing: Tokens = quote! {
+        ::std::result::Result::Err(::jargon_args::Error::MissingArg(_))
+            | ::std::result::Result::Err(::jargon_args::Error::MissingArgEnv { .. })
+    };
+
+    Ok(match &f.kind {
+        _ if f.count => quote!(matches.count(#key) as #ty),
+        Kind::Flag => quote!(matches.contains(#key)),
+        Kind::Optional(inner) => quote! {
+            match matches.result_arg::<#inner, &str>(#key) {
+                ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                #missing => ::std::option::Option::None,
+                ::std::result::Result::Err(e) => return ::std::result::Result::Err(e),
+            }
+        },
+        Kind::Multiple(inner) => quote! {
+            match matches.result_args::<#inner, &str>(#key) {
+                ::std::result::Result::Ok(values) => values,
+                #missing => ::std::vec::Vec::new(),
+                ::std::result::Result::Err(e) => return ::std::result::Result::Err(e),
+            }
+        },
+        Kind::Required(_) => quote!(matches.result_arg::<#ty, &str>(#key)?),
+    })
+}
+
+/// Renders the expression taking a positional field's value out of `rest`.
+fn positional(f: &Field) -> syn::Result<Tokens> {
+    let name: &str = &f.value;
+    let parse = |ty: &Type| {
+        quote! {
+            |value: ::std::string::String| {
+                value.parse::<#ty>().map_err(|e| ::jargon_args::Error::InvalidValue {
+                    key: ::jargon_args::Key::from(#name),
+                    value: value.clone(),
+                    message: e.to_string(),
+                    occurrence: ::std::option::Option::None,
+                })
+            }
+        }
+    };
+
+    Ok(match &f.kind {
+        Kind::Flag => {
+            return Err(syn::Error::new_spanned(
+                &f.ty,
+                "positional fields can't be `bool`",
+            ))
+        }
+        Kind::Optional(inner) => {
+            let parse: Tokens = parse(inner);
+            quote!(rest.pop_front().map(#parse).transpose()?)
+        }
+        Kind::Multiple(inner) => {
+            let parse: Tokens = parse(inner);
+            quote! {
+                rest.drain(..)
+                    .map(#parse)
+                    .collect::<::std::result::Result<::std::vec::Vec<#inner>, _>>()?
+            }
+        }
+        Kind::Required(ty) => {
+            let parse: Tokens = parse(ty);
+            quote! {
+                (#parse)(rest.pop_front().ok_or_else(|| {
+                    ::jargon_args::Error::MissingArg(::jargon_args::Key::from(#name))
+                })?)?
+            }
+        }
+    })
+}
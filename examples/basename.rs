@@ -1,4 +1,4 @@
-use jargon_args::Jargon;
+use jargon_args::{Arg, Jargon, Spec};
 
 // Not required, but a helper struct to contain arguments and their data.
 struct Args {
@@ -10,13 +10,22 @@ struct Args {
 
 fn main() {
     let mut j: Jargon = Jargon::from_env(); // Get an instance of Jargon using `std::env::args()`
+    let spec: Spec = spec(); // declare arguments once, for both parsing and help
 
-    if j.contains(["-h", "--help"]) { // check for help argument
-        print!("{}", HELP);
+    let m = match spec.parse(&mut j) { // take declared arguments out of Jargon
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    if m.contains("--help") { // check for help argument
+        print!("{}", spec.help());
         return;
     }
 
-    if j.contains(["-v", "--version"]) { // check for version argument
+    if m.contains("--version") { // check for version argument
         println!(
             "basename example for Jargon crate {}",
             env!("CARGO_PKG_VERSION")
@@ -25,9 +34,9 @@ fn main() {
     }
 
     let args = Args { // fill helper struct
-        multiple: j.contains(["-a", "--multiple"]), // multiple
-        suffix: j.option_arg(["-s", "--suffix"]), // suffix to remove
-        zero: j.contains(["-z", "--zero"]), // terminate lines with null
+        multiple: m.contains("--multiple"), // multiple
+        suffix: m.option_arg("--suffix"), // suffix to remove
+        zero: m.contains("--zero"), // terminate lines with null
         names: j.finish(), // get names
     };
 
@@ -51,7 +60,7 @@ fn main() {
 
 // extract basename and remove suffix
 fn print(args: &Args, name: &String) -> String {
-    if let Some(name) = name.split('/').last() {
+    if let Some(name) = name.split('/').next_back() {
         if let Some(suffix) = &args.suffix {
             if name.ends_with(suffix) {
                 if let Some(n) = name.strip_suffix(suffix) {
@@ -64,14 +73,23 @@ fn print(args: &Args, name: &String) -> String {
     name.to_string()
 }
 
-const HELP: &str = "Usage: basename NAME [SUFFIX]
-  or:  basename OPTION... NAME...
-Print NAME with any leading directory components removed.
-If specified, also remove a trailing SUFFIX.
-
-  -a, --multiple       support multiple arguments and treat each as a NAME
-  -s, --suffix SUFFIX  remove a trailing SUFFIX; implies -a
-  -z, --zero           end each output line with NUL, not newline
-  -h, --help     display this help and exit
-  -v, --version  output version information and exit
-";
+fn spec() -> Spec {
+    Spec::new("basename")
+        .usage("NAME [SUFFIX]")
+        .usage("OPTION... NAME...")
+        .about(
+            "Print NAME with any leading directory components removed.
+If specified, also remove a trailing SUFFIX.",
+        )
+        .arg(
+            Arg::flag(["-a", "--multiple"])
+                .help("support multiple arguments and treat each as a NAME"),
+        )
+        .arg(
+            Arg::option(["-s", "--suffix"], "SUFFIX")
+                .help("remove a trailing SUFFIX; implies -a"),
+        )
+        .arg(Arg::flag(["-z", "--zero"]).help("end each output line with NUL, not newline"))
+        .arg(Arg::flag(["-h", "--help"]).help("display this help and exit"))
+        .arg(Arg::flag(["-v", "--version"]).help("output version information and exit"))
+}
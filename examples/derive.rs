@@ -0,0 +1,64 @@
+use jargon_args::{Error, FromJargon, Jargon};
+
+/// Print NAME with any leading directory components removed.
+#[derive(Jargon)]
+#[jargon(
+    name = "basename",
+    usage = "NAME [SUFFIX]",
+    usage = "OPTION... NAME..."
+)]
+struct Args {
+    /// support multiple arguments and treat each as a NAME
+    #[jargon(short = 'a', long)]
+    multiple: bool,
+    /// remove a trailing SUFFIX; implies -a
+    #[jargon(short, long, value = "SUFFIX")]
+    suffix: Option<String>,
+    /// end each output line with NUL, not newline
+    #[jargon(short, long)]
+    zero: bool,
+    /// display this help and exit
+    #[jargon(short, long, help)]
+    help: bool,
+    #[jargon(positional)]
+    names: Vec<String>,
+}
+
+fn main() {
+    let args: Args = match Args::from_env() {
+        // parse arguments straight into the struct
+        Ok(args) => args,
+        Err(Error::Help(help)) => {
+            // help text comes from the same declarations
+            print!("{}", help);
+            return;
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    if args.names.is_empty() {
+        // check if there are names
+        println!("Missing NAMES");
+        return;
+    }
+
+    let multiple: bool = args.multiple || args.suffix.is_some();
+    let count: usize = if multiple { args.names.len() } else { 1 };
+
+    for name in args.names.iter().take(count) {
+        let mut name: &str = name.rsplit('/').next().unwrap_or(name); // extract basename
+        if let Some(suffix) = &args.suffix {
+            name = name.strip_suffix(suffix.as_str()).unwrap_or(name); // remove suffix
+        }
+
+        if args.zero {
+            // terminate with null if `-z` or `--zero` was passed
+            print!("{}\0", name);
+        } else {
+            println!("{}", name);
+        }
+    }
+}
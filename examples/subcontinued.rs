@@ -1,22 +1,36 @@
-use jargon_args::Jargon;
+use jargon_args::{Arg, Context, Error, Jargon};
 
 fn main() {
     let mut j: Jargon = Jargon::from_env();
-    j.on_subcommand("go", go);
+    j.positional_subcommands(); // subcommands are the first argument that isn't a key
+
+    let mut ctx: Context = Context::new().global(Arg::flag(["-v", "--verbose"])); // given at any level
+
+    if let Err(e) = j.ctx_on_subcommand(&mut ctx, "go", go) {
+        eprintln!("{}", e);
+    }
 }
 
-fn go(v: Vec<String>) {
+fn go(v: Vec<String>, ctx: &mut Context) -> Result<(), Error> {
     println!("go!");
     let mut j: Jargon = Jargon::from_vec(v);
-    j.on_subcommand("goo", goo)
+    j.positional_subcommands();
+    j.ctx_on_subcommand(ctx, "goo", goo)
 }
 
-fn goo(v: Vec<String>) {
+fn goo(v: Vec<String>, ctx: &mut Context) -> Result<(), Error> {
     println!("goo!");
     let mut j: Jargon = Jargon::from_vec(v);
-    j.on_subcommand("gooo", gooo)
+    j.positional_subcommands();
+    j.ctx_on_subcommand(ctx, "gooo", gooo)
 }
 
-fn gooo(_: Vec<String>) {
-    println!("gooo!");
+fn gooo(v: Vec<String>, ctx: &mut Context) -> Result<(), Error> {
+    ctx.parse(&mut Jargon::from_vec(v))?; // take global options given after the last subcommand
+    if ctx.contains("--verbose") {
+        println!("gooo, verbosely!");
+    } else {
+        println!("gooo!");
+    }
+    Ok(())
 }
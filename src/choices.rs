@@ -0,0 +1,99 @@
+use super::Error;
+use super::Key;
+use std::ffi::OsString;
+
+/// # Choices
+///
+/// This trait is for types, usually enums, whose values are spelled as one of a fixed set of
+/// words, like `--format json|yaml|csv`. `Jargon::result_choice` and `Matches::result_choice`
+/// take them, and any other spelling is an error listing the accepted ones.
+///
+/// ```
+/// use jargon_args::{Choices, Error, Jargon};
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Format {
+///     Json,
+///     Yaml,
+///     Csv,
+/// }
+///
+/// impl Choices for Format {
+///     fn choices() -> Vec<(&'static str, Self)> {
+///         vec![
+///             ("json", Format::Json),
+///             ("yaml", Format::Yaml),
+///             ("yml", Format::Yaml),
+///             ("csv", Format::Csv),
+///         ]
+///     }
+///
+///     fn ignore_case() -> bool {
+///         true
+///     }
+/// }
+///
+/// let mut j: Jargon = Jargon::from_vec(vec!["report", "--format", "YML", "-o", "xml"]);
+/// assert_eq!(j.result_choice("--format"), Ok(Format::Yaml));
+/// assert_eq!(
+///     j.result_choice::<Format, &str>("-o"),
+///     Err(Error::InvalidChoice {
+///         key: "-o".into(),
+///         value: "xml".to_string(),
+///         choices: vec!["json", "yaml", "yml", "csv"]
+///             .into_iter()
+///             .map(String::from)
+///             .collect(),
+///     })
+/// );
+/// ```
+pub trait Choices: Sized {
+    /// Every accepted spelling with the value it stands for, in the order errors and help list
+    /// them. A value may have more than one spelling.
+    fn choices() -> Vec<(&'static str, Self)>;
+
+    /// Whether spellings match whatever their case. False unless implemented.
+    fn ignore_case() -> bool {
+        false
+    }
+
+    /// Every accepted spelling, to declare them with `Arg::choices`.
+    fn spellings() -> Vec<&'static str> {
+        Self::choices().into_iter().map(|(s, _)| s).collect()
+    }
+}
+
+/// Returns the value of T spelled value. `InvalidChoice` if it is no spelling of T.
+pub(crate) fn choose<T: Choices>(key: &Key, value: OsString) -> Result<T, Error> {
+    let mut choices: Vec<(&str, T)> = T::choices();
+    let spellings: Vec<&str> = choices.iter().map(|(s, _)| *s).collect();
+    let i: usize = pick(key, value, &spellings, T::ignore_case())?;
+    Ok(choices.swap_remove(i).1)
+}
+
+/// Returns the index of the choice value is. `InvalidChoice` listing choices if it is none of
+/// them.
+pub(crate) fn pick<S: AsRef<str>>(
+    key: &Key,
+    value: OsString,
+    choices: &[S],
+    ignore_case: bool,
+) -> Result<usize, Error> {
+    let value: String = value.to_string_lossy().into_owned();
+    let matches = |choice: &str| {
+        if ignore_case {
+            choice.to_lowercase() == value.to_lowercase()
+        } else {
+            choice == value
+        }
+    };
+
+    match choices.iter().position(|choice| matches(choice.as_ref())) {
+        Some(i) => Ok(i),
+        None => Err(Error::InvalidChoice {
+            key: key.clone(),
+            value,
+            choices: choices.iter().map(|c| c.as_ref().to_string()).collect(),
+        }),
+    }
+}
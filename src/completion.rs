@@ -0,0 +1,430 @@
+use super::Error;
+use super::Key;
+use super::Spec;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// # Shell
+///
+/// This Enum names the shells a Spec can write a completion script for. It parses from `bash`,
+/// `zsh` and `fish`, so it can be taken straight from the arguments of a `completions`
+/// subcommand.
+///
+/// ```
+/// use jargon_args::{Arg, Shell, Spec};
+///
+/// let spec: Spec = Spec::new("basename")
+///     .arg(Arg::flag(["-a", "--multiple"]).help("support multiple arguments"))
+///     .arg(Arg::option(["-s", "--suffix"], "SUFFIX").help("remove a trailing SUFFIX"));
+///
+/// let shell: Shell = "fish".parse().unwrap();
+/// assert!(spec
+///     .completions(shell)
+///     .contains("complete -c 'basename' -s a -l 'multiple' -d 'support multiple arguments'"));
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum Shell {
+    /// # Bash
+    ///
+    /// The script defines a function and registers it with `complete -F`.
+    Bash,
+
+    /// # Zsh
+    ///
+    /// The script is a `#compdef` function for a directory in `$fpath`, which can also be sourced.
+    Zsh,
+
+    /// # Fish
+    ///
+    /// The script is a list of `complete` commands for `~/.config/fish/completions`.
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            s => Err(Error::Other(format!(
+                "Unknown shell: '{}', expected 'bash', 'zsh' or 'fish'",
+                s
+            ))),
+        }
+    }
+}
+
+impl Display for Shell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shell::Bash => write!(f, "bash"),
+            Shell::Zsh => write!(f, "zsh"),
+            Shell::Fish => write!(f, "fish"),
+        }
+    }
+}
+
+impl Spec {
+    /// Renders a completion script for shell, completing every declared argument and
+    /// subcommand, nested ones included. Files are completed after options that take a value,
+    /// and wherever nothing declared matches.
+    pub fn completions(&self, shell: Shell) -> String {
+        let mut commands: Vec<(Vec<&str>, &Spec)> = Vec::new();
+        walk(self, vec![self.name.as_str()], &mut commands);
+
+        match shell {
+            Shell::Bash => bash(self, &commands),
+            Shell::Zsh => zsh(self, &commands),
+            Shell::Fish => fish(self, &commands),
+        }
+    }
+}
+
+/// Lists spec and its subcommands, depth first, each with the names leading to it.
+fn walk<'a>(spec: &'a Spec, path: Vec<&'a str>, commands: &mut Vec<(Vec<&'a str>, &'a Spec)>) {
+    commands.push((path.clone(), spec));
+    for subcommand in &spec.subcommands {
+        let mut path: Vec<&str> = path.clone();
+        path.push(&subcommand.name);
+        walk(subcommand, path, commands);
+    }
+}
+
+/// Turns the names leading to a command into a name usable for shell functions.
+fn ident(path: &[&str]) -> String {
+    path.iter()
+        .map(|name| {
+            name.chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect()
+        })
+        .collect::<Vec<String>>()
+        .join("__")
+}
+
+/// Quotes s in single quotes, as bash, zsh and fish all read them.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+fn bash(spec: &Spec, commands: &[(Vec<&str>, &Spec)]) -> String {
+    let id: String = ident(&[&spec.name]);
+    let mut script: String = format!(
+        "_{}() {{
+    local cur prev cmd i
+    cur=\"${{COMP_WORDS[COMP_CWORD]}}\"
+    prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"
+    if [[ \"${{prev}}\" == \"=\" && ${{COMP_CWORD}} -gt 1 ]]; then
+        prev=\"${{COMP_WORDS[COMP_CWORD-2]}}\"
+    fi
+    cmd={}
+",
+        id, id
+    );
+
+    if !spec.subcommands.is_empty() {
+        script.push_str(
+            "
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case \"${cmd},${COMP_WORDS[i]}\" in
+",
+        );
+        for (path, command) in commands {
+            for subcommand in &command.subcommands {
+                let mut child: Vec<&str> = path.clone();
+                child.push(&subcommand.name);
+                script.push_str(&format!(
+                    "            {}) cmd={} ;;\n",
+                    quote(&format!("{},{}", ident(path), subcommand.name)),
+                    ident(&child)
+                ));
+            }
+        }
+        script.push_str("        esac\n    done\n");
+    }
+
+    script.push_str("\n    case \"${cmd}\" in\n");
+    for (path, command) in commands {
+        script.push_str(&format!("        {})\n", ident(path)));
+
+        let mut replies: Vec<(Vec<String>, String)> = command
+            .args
+            .iter()
+            .filter(|arg| arg.value.is_some() && !arg.choices.is_empty())
+            .map(|arg| {
+                let words: String = quote(&arg.choices.join(" "));
+                (arg.key.spellings(), format!("compgen -W {} --", words))
+            })
+            .collect();
+        let files: Vec<String> = command
+            .args
+            .iter()
+            .filter(|arg| arg.value.is_some() && arg.choices.is_empty())
+            .flat_map(|arg| arg.key.spellings())
+            .collect();
+        if !files.is_empty() {
+            replies.push((files, "compgen -f --".to_string()));
+        }
+
+        if !replies.is_empty() {
+            script.push_str("            case \"${prev}\" in\n");
+            for (keys, reply) in replies {
+                script.push_str(&format!(
+                    "                {})
+                    COMPREPLY=($({} \"${{cur}}\"))
+                    return 0
+                    ;;
+",
+                    keys.iter()
+                        .map(|k| quote(k))
+                        .collect::<Vec<String>>()
+                        .join("|"),
+                    reply
+                ));
+            }
+            script.push_str("            esac\n");
+        }
+
+        let words: Vec<String> = command
+            .args
+            .iter()
+            .flat_map(|arg| arg.key.spellings())
+            .chain(command.subcommands.iter().map(|s| s.name.clone()))
+            .collect();
+        script.push_str(&format!(
+            "            COMPREPLY=($(compgen -W {} -- \"${{cur}}\"))\n            ;;\n",
+            quote(&words.join(" "))
+        ));
+    }
+    script.push_str("    esac\n}\n\n");
+
+    script.push_str(&format!(
+        "complete -o default -F _{} {}\n",
+        id,
+        quote(&spec.name)
+    ));
+    script
+}
+
+/// Escapes s for the inside of a quoted `_arguments` spec or `_describe` entry.
+fn zsh_escape(s: &str) -> String {
+    let mut escaped: String = String::new();
+    for c in s.chars() {
+        match c {
+            '\\' | '[' | ']' | ':' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\'' => escaped.push_str("'\\''"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn zsh(spec: &Spec, commands: &[(Vec<&str>, &Spec)]) -> String {
+    let id: String = ident(&[&spec.name]);
+    let mut script: String = format!("#compdef {}\n", spec.name);
+
+    for (path, command) in commands {
+        script.push_str(&format!(
+            "
+_{}() {{
+    local context state state_descr line
+    typeset -A opt_args
+
+    _arguments -s -S",
+            ident(path)
+        ));
+
+        for arg in command.args.iter().filter(|arg| !arg.key.is_sub()) {
+            let help: String = if arg.help.is_empty() {
+                String::new()
+            } else {
+                format!("[{}]", zsh_escape(&arg.help))
+            };
+            let value: String = match &arg.value {
+                Some(value) if !arg.choices.is_empty() => format!(
+                    ":{}:({})",
+                    zsh_escape(value),
+                    arg.choices
+                        .iter()
+                        .map(|c| zsh_escape(c).replace(' ', "\\ "))
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                ),
+                Some(value) => format!(":{}:_files", zsh_escape(value)),
+                None => String::new(),
+            };
+            let with_value = |key: String, long: bool| match (&arg.value, long) {
+                (None, _) => key,
+                (Some(_), true) => format!("{}=", key),
+                (Some(_), false) => format!("{}+", key),
+            };
+
+            let spec: String = match &arg.key {
+                Key::Dual { .. } => {
+                    let spellings: Vec<String> = arg.key.spellings();
+                    format!(
+                        "'({})'{{{},{}}}'{}{}'",
+                        spellings.join(" "),
+                        with_value(spellings[0].clone(), false),
+                        with_value(spellings[1].clone(), true),
+                        help,
+                        value
+                    )
+                }
+                key => format!(
+                    "'{}{}{}'",
+                    with_value(key.to_string(), key.is_long()),
+                    help,
+                    value
+                ),
+            };
+            script.push_str(&format!(" \\\n        {}", spec));
+        }
+
+        if command.subcommands.is_empty() {
+            script.push_str(" \\\n        '*:file:_files'\n}\n");
+            continue;
+        }
+
+        script.push_str(
+            " \\
+        '1: :->command' \\
+        '*:: :->argument'
+
+    case ${state} in
+        command)
+            local -a commands
+            commands=(
+",
+        );
+        for subcommand in &command.subcommands {
+            let mut entry: String = zsh_escape(&subcommand.name);
+            if !subcommand.summary().is_empty() {
+                entry.push_str(&format!(":{}", zsh_escape(subcommand.summary())));
+            }
+            script.push_str(&format!("                '{}'\n", entry));
+        }
+        script.push_str(
+            "            )
+            _describe -t commands 'command' commands
+            ;;
+        argument)
+            case ${words[1]} in
+",
+        );
+        for subcommand in &command.subcommands {
+            let mut child: Vec<&str> = path.clone();
+            child.push(&subcommand.name);
+            script.push_str(&format!(
+                "                {}) _{} ;;\n",
+                quote(&subcommand.name),
+                ident(&child)
+            ));
+        }
+        script.push_str("            esac\n            ;;\n    esac\n}\n");
+    }
+
+    script.push_str(&format!(
+        "
+if [ \"$funcstack[1]\" = \"_{}\" ]; then
+    _{} \"$@\"
+else
+    compdef _{} {}
+fi
+",
+        id,
+        id,
+        id,
+        quote(&spec.name)
+    ));
+    script
+}
+
+/// Quotes s in single quotes the way fish reads them.
+fn fish_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn fish(spec: &Spec, commands: &[(Vec<&str>, &Spec)]) -> String {
+    let id: String = ident(&[&spec.name]);
+    let name: String = fish_quote(&spec.name);
+    let mut script: String = String::new();
+
+    let nested: bool = !spec.subcommands.is_empty();
+    if nested {
+        script.push_str(&format!(
+            "function __fish_{}_command
+    set -l cmd {}
+    set -l words (commandline -opc)
+    set -e words[1]
+    for word in $words
+        switch \"$cmd,$word\"
+",
+            id, id
+        ));
+        for (path, command) in commands {
+            for subcommand in &command.subcommands {
+                let mut child: Vec<&str> = path.clone();
+                child.push(&subcommand.name);
+                script.push_str(&format!(
+                    "            case {}\n                set cmd {}\n",
+                    fish_quote(&format!("{},{}", ident(path), subcommand.name)),
+                    ident(&child)
+                ));
+            }
+        }
+        script.push_str("        end\n    end\n    echo $cmd\nend\n\n");
+    }
+
+    for (path, command) in commands {
+        let condition: String = if nested {
+            format!(" -n 'test (__fish_{}_command) = {}'", id, ident(path))
+        } else {
+            String::new()
+        };
+
+        for arg in &command.args {
+            let mut line: String = format!("complete -c {}{}", name, condition);
+            match &arg.key {
+                Key::Dual { s_txt, l_txt, .. } => {
+                    line.push_str(&format!(" -s {} -l {}", s_txt, fish_quote(l_txt)))
+                }
+                Key::Long { txt, .. } => line.push_str(&format!(" -l {}", fish_quote(txt))),
+                Key::Short { txt, .. } => line.push_str(&format!(" -s {}", txt)),
+                Key::Sub { txt } => line.push_str(&format!(" -f -a {}", fish_quote(txt))),
+            }
+            if arg.value.is_some() {
+                line.push_str(" -r");
+            }
+            if !arg.choices.is_empty() {
+                line.push_str(&format!(" -f -a {}", fish_quote(&arg.choices.join(" "))));
+            }
+            if !arg.help.is_empty() {
+                line.push_str(&format!(" -d {}", fish_quote(&arg.help)));
+            }
+            script.push_str(&line);
+            script.push('\n');
+        }
+
+        for subcommand in &command.subcommands {
+            let mut line: String = format!(
+                "complete -c {}{} -f -a {}",
+                name,
+                condition,
+                fish_quote(&subcommand.name)
+            );
+            if !subcommand.summary().is_empty() {
+                line.push_str(&format!(" -d {}", fish_quote(subcommand.summary())));
+            }
+            script.push_str(&line);
+            script.push('\n');
+        }
+    }
+
+    script
+}
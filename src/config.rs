@@ -0,0 +1,193 @@
+use super::Error;
+use super::Jargon;
+use super::Key;
+use super::Source;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+impl Jargon {
+    /// Loads the config files of app, `/etc/<app>.conf` then `~/.config/<app>/config`, skipping
+    /// those that don't exist. The second is in `$XDG_CONFIG_HOME` instead of `~/.config` when
+    /// it is set. With the `toml` feature, `/etc/<app>.toml` and `~/.config/<app>/config.toml`
+    /// are loaded after each.
+    ///
+    /// See `config_file` for how entries are read and used.
+    pub fn config_files(&mut self, app: &str) -> Result<(), Error> {
+        let mut files: Vec<PathBuf> = vec![PathBuf::from(format!("/etc/{}.conf", app))];
+
+        let home: Option<PathBuf> = match self.var("XDG_CONFIG_HOME").map(PathBuf::from) {
+            Some(dir) if dir.is_absolute() => Some(dir),
+            _ => self
+                .var("HOME")
+                .map(|home| Path::new(&home).join(".config")),
+        };
+        if let Some(home) = home {
+            files.push(home.join(app).join("config"));
+        }
+
+        for file in files {
+            self.load(&file, false)?;
+            if cfg!(feature = "toml") {
+                self.load(&file.with_extension("toml"), false)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads a config file of `key = value` lines, where blank lines and lines starting with `#`
+    /// or `;` are skipped, and values may be quoted. With the `toml` feature, files ending in
+    /// `.toml` are read as TOML tables of strings, numbers and booleans instead.
+    ///
+    /// Entries are the values of the keys they name, without dashes, so `color = never` is the
+    /// value of `--color` and `v = true` gives `-v`. Arguments win over environment variables,
+    /// which win over config files, which win over defaults, and of two files the last loaded
+    /// wins. `source` tells which file a value came from.
+    ///
+    /// Err(jargon_args::Error::Config) if the file can't be read, or has a line that isn't an
+    /// entry.
+    ///
+    /// ```
+    /// use jargon_args::{Jargon, Source};
+    ///
+    /// let file = std::env::temp_dir().join("jargon_config_file_doc");
+    /// std::fs::write(&file, "# defaults\ncolor = never\nwidth = 80\n").unwrap();
+    ///
+    /// let mut j: Jargon = Jargon::from_vec(vec!["ls", "--width", "120"]);
+    /// j.config_file(&file).unwrap();
+    ///
+    /// assert_eq!(j.option_arg("--color"), Some("never".to_string()));
+    /// assert_eq!(j.source("--color"), Some(Source::Config(file)));
+    /// assert_eq!(j.option_arg("--width"), Some(120));
+    /// assert_eq!(j.source("--width"), Some(Source::CommandLine));
+    /// ```
+    pub fn config_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        self.load(path.as_ref(), true)
+    }
+
+    /// Reads the entries of file, an error if it doesn't exist and is required.
+    fn load(&mut self, file: &Path, required: bool) -> Result<(), Error> {
+        let text: String = match std::fs::read_to_string(file) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound && !required => return Ok(()),
+            Err(e) => return Err(error(file, None, e.to_string())),
+        };
+
+        #[cfg(feature = "toml")]
+        let entries: Vec<(String, String)> = match file.extension() {
+            Some(ext) if ext == "toml" => parse_toml(file, &text)?,
+            _ => parse(file, &text)?,
+        };
+        #[cfg(not(feature = "toml"))]
+        let entries: Vec<(String, String)> = parse(file, &text)?;
+        self.1.config.extend(
+            entries
+                .into_iter()
+                .map(|(name, value)| (name, value, file.to_path_buf())),
+        );
+        Ok(())
+    }
+
+    /// Returns the value of key in the loaded config files, and the file it came from.
+    pub(crate) fn configured(&self, key: &Key) -> Option<(String, PathBuf)> {
+        let names: Vec<String> = key
+            .spellings()
+            .iter()
+            .map(|s| s.trim_start_matches('-').to_string())
+            .collect();
+
+        self.1
+            .config
+            .iter()
+            .rev()
+            .find(|(name, _, _)| names.iter().any(|n| n == name))
+            .map(|(_, value, file)| (value.clone(), file.clone()))
+    }
+
+    /// Returns how many times key is given by the loaded config files, from a count or a
+    /// boolean. 0 for anything else.
+    pub(crate) fn configured_count(&mut self, key: &Key) -> usize {
+        let (value, file) = match self.configured(key) {
+            Some(found) => found,
+            None => return 0,
+        };
+
+        let n: usize = match value.to_lowercase().as_str() {
+            "true" | "yes" | "on" => 1,
+            "false" | "no" | "off" => 0,
+            value => value.parse().unwrap_or(0),
+        };
+        if n > 0 {
+            self.record(key, Source::Config(file));
+        }
+        n
+    }
+}
+
+fn error(file: &Path, line: Option<usize>, message: String) -> Error {
+    Error::Config {
+        file: file.display().to_string(),
+        line,
+        message,
+    }
+}
+
+/// Reads the `key = value` lines of a config file.
+fn parse(file: &Path, text: &str) -> Result<Vec<(String, String)>, Error> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line: &str = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        let (name, value) = match line.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => (name.trim(), value.trim()),
+            _ => {
+                return Err(error(
+                    file,
+                    Some(i + 1),
+                    format!("expected 'key = value', found '{}'", line),
+                ))
+            }
+        };
+        entries.push((name.trim_start_matches('-').to_string(), unquote(value)));
+    }
+    Ok(entries)
+}
+
+/// Removes the quotes around a value, if it has matching ones.
+fn unquote(value: &str) -> String {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return value[1..value.len() - 1].to_string();
+        }
+    }
+    value.to_string()
+}
+
+/// Reads the entries of a TOML config file.
+#[cfg(feature = "toml")]
+fn parse_toml(file: &Path, text: &str) -> Result<Vec<(String, String)>, Error> {
+    let table: toml::Table = text.parse().map_err(|e: toml::de::Error| {
+        let line: Option<usize> = e
+            .span()
+            .map(|span| text[..span.start].matches('\n').count() + 1);
+        error(file, line, e.message().to_string())
+    })?;
+
+    table
+        .into_iter()
+        .map(|(name, value)| match value {
+            toml::Value::String(value) => Ok((name, value)),
+            toml::Value::Integer(value) => Ok((name, value.to_string())),
+            toml::Value::Float(value) => Ok((name, value.to_string())),
+            toml::Value::Boolean(value) => Ok((name, value.to_string())),
+            _ => Err(error(
+                file,
+                None,
+                format!("'{}' must be a string, number or boolean", name),
+            )),
+        })
+        .collect()
+}
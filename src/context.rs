@@ -0,0 +1,123 @@
+use super::Arg;
+use super::Error;
+use super::Jargon;
+use super::Key;
+use super::Matches;
+use super::Source;
+use super::Spec;
+use std::ops::Deref;
+
+/// # Context
+///
+/// This struct carries global options through a chain of subcommands. Global options are
+/// declared once, taken out of arguments at every level, and can be read from any nested
+/// handler, as the context is passed to each alongside its arguments. It reads like Matches.
+///
+/// Counts add up across levels and values are kept in the order they were given, so a global
+/// option given both before and after a subcommand is seen by the handlers of both.
+///
+/// ```
+/// use jargon_args::{Arg, Context, Error, Jargon};
+///
+/// let mut ctx: Context = Context::new()
+///     .global(Arg::flag(["-v", "--verbose"]))
+///     .global(Arg::option("--color", "WHEN").default("auto"));
+///
+/// let mut j: Jargon = Jargon::from_vec(vec!["app", "-v", "go", "goo", "--verbose"]);
+/// j.positional_subcommands();
+///
+/// let verbosity: Result<usize, Error> = j.ctx_on_subcommand(&mut ctx, "go", |args, ctx| {
+///     let mut j: Jargon = Jargon::from_vec(args);
+///     j.positional_subcommands();
+///     j.ctx_on_subcommand(ctx, "goo", |_, ctx| Ok(ctx.count("-v")))
+/// });
+///
+/// assert_eq!(verbosity, Ok(2));
+/// assert_eq!(ctx.option_arg("--color"), Some("auto".to_string()));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Context {
+    spec: Spec,
+    matches: Matches,
+}
+
+impl Context {
+    /// Starts a context without any global options.
+    pub fn new() -> Self {
+        Self {
+            spec: Spec::default(),
+            matches: Matches(Vec::new()),
+        }
+    }
+
+    /// Declares a global option.
+    pub fn global(mut self, arg: Arg) -> Self {
+        self.matches.0.push((arg.clone(), 0, Vec::new(), None));
+        self.spec = self.spec.arg(arg);
+        self
+    }
+
+    /// Removes every global option from provided Jargon, adding them to the context.
+    /// Err(jargon_args::Error) if an option is given without a value.
+    pub fn parse(&mut self, jargon: &mut Jargon) -> Result<(), Error> {
+        let found: Matches = self.spec.parse(jargon)?;
+
+        for ((_, count, values, source), (_, new_count, new_values, new_source)) in
+            self.matches.0.iter_mut().zip(found.0)
+        {
+            if new_source == Some(Source::CommandLine) {
+                // Values from arguments replace ones taken from the environment or a config file.
+                if *source != new_source {
+                    *count = 0;
+                    values.clear();
+                }
+                *count += new_count;
+                values.extend(new_values);
+                *source = new_source;
+            } else if source.is_none() {
+                *count = new_count;
+                *values = new_values;
+                *source = new_source;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Context {
+    type Target = Matches;
+
+    fn deref(&self) -> &Matches {
+        &self.matches
+    }
+}
+
+impl Jargon {
+    /// Works like `res_on_subcommand`, but first takes the global options of context out of
+    /// arguments, then passes the context to function with the subcommand's arguments.
+    pub fn ctx_on_subcommand<K, F, T>(
+        &mut self,
+        ctx: &mut Context,
+        key: K,
+        mut f: F,
+    ) -> Result<T, Error>
+    where
+        K: Into<Key>,
+        F: FnMut(Vec<String>, &mut Context) -> Result<T, Error>,
+    {
+        ctx.parse(self)?;
+
+        let key: Key = key.into();
+        self.query(&key);
+        match self.enter(&key)? {
+            Some(args) => f(args, ctx),
+            None => Err(Error::MissingArg(key)),
+        }
+    }
+}
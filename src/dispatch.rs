@@ -0,0 +1,100 @@
+use super::Error;
+use super::Jargon;
+use super::Key;
+
+type Handler<'a, T> = Box<dyn FnOnce(Vec<String>) -> Result<T, Error> + 'a>;
+
+/// # Dispatch
+///
+/// This struct picks one of many subcommands in a single pass over the arguments, and runs its
+/// handler. Made with `Jargon::dispatch`. The subcommand is the first argument before `--` that
+/// isn't a key, and its handler gets the same arguments as with `res_on_subcommand`.
+///
+/// Options before the subcommand whose values are separate arguments, like `--name list`,
+/// must be taken before running it so their values are not taken for the subcommand.
+///
+/// ```
+/// use jargon_args::{Error, Jargon};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Command {
+///     List,
+///     Add(Vec<String>),
+/// }
+///
+/// let mut j: Jargon = Jargon::from_vec(vec!["todo", "add", "milk", "eggs"]);
+/// let command: Result<Command, Error> = j
+///     .dispatch()
+///     .on("list", |_| Ok(Command::List))
+///     .on("add", |args| Ok(Command::Add(args[1..].to_vec())))
+///     .run();
+///
+/// assert_eq!(
+///     command,
+///     Ok(Command::Add(vec!["milk".to_string(), "eggs".to_string()]))
+/// );
+/// ```
+pub struct Dispatch<'a, T> {
+    jargon: &'a mut Jargon,
+    handlers: Vec<(Key, Handler<'a, T>)>,
+}
+
+impl Jargon {
+    /// Starts a Dispatch over the subcommands in this Jargon.
+    pub fn dispatch<T>(&mut self) -> Dispatch<'_, T> {
+        Dispatch {
+            jargon: self,
+            handlers: Vec::new(),
+        }
+    }
+}
+
+impl<'a, T> Dispatch<'a, T> {
+    /// Registers the handler of a subcommand.
+    pub fn on<K, F>(mut self, key: K, f: F) -> Self
+    where
+        K: Into<Key>,
+        F: FnOnce(Vec<String>) -> Result<T, Error> + 'a,
+    {
+        self.handlers.push((key.into(), Box::new(f)));
+        self
+    }
+
+    /// Runs the handler of the subcommand given, and returns what it returns.
+    /// Err(jargon_args::Error) listing every registered subcommand, and the closest one if it is
+    /// a likely typo, if the first argument that isn't a key is none of them, or listing the
+    /// ones an ambiguous abbreviation could be.
+    pub fn run(self) -> Result<T, Error> {
+        let Dispatch { jargon, handlers } = self;
+        for (key, _) in &handlers {
+            jargon.query(key);
+        }
+
+        let expected: Vec<Key> = handlers.iter().map(|(key, _)| key.clone()).collect();
+        let (i, arg) = match jargon.positional() {
+            Some(found) => found,
+            None => {
+                return Err(Error::UnknownSubcommand {
+                    arg: None,
+                    suggestion: None,
+                    expected,
+                })
+            }
+        };
+
+        if let Some(whole) = jargon.0[i].to_str() {
+            for (key, f) in handlers {
+                if key.is_sub() && jargon.names(whole, &key)? {
+                    return f(jargon.split(i));
+                }
+            }
+        }
+
+        let unknown = jargon.unknown(i, &arg, true);
+        Err(Error::UnknownSubcommand {
+            arg: Some(unknown.arg),
+            suggestion: unknown.suggestion,
+            expected,
+        })
+    }
+}
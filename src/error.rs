@@ -3,13 +3,80 @@ use std::fmt::{Display, Formatter};
 
 /// # Error
 ///
-/// This Enum currently contains two variants, MissingArgs and Other.
+/// This Enum currently contains fifteen variants, MissingArgs, MissingArgEnv, MissingValue,
+/// InvalidValue, InvalidChoice, UnknownArgs, UnknownSubcommand, Ambiguous, Conflict, Requires,
+/// RequiredUnless, ResponseFile, Config, Help and Other.
 ///
 /// ## MissingArgs(Key)
 ///
 /// This variant is used internally by Jargon to warn when a required argument
 /// (from the `res_*` functions) does not exist.
 ///
+/// ## MissingArgEnv { key, var }
+///
+/// This variant is used internally by Jargon to warn when a required argument does not exist,
+/// and neither does the environment variable it falls back to.
+///
+/// ## MissingValue(Key)
+///
+/// This variant is used internally by Jargon to warn when a required argument is given,
+/// but without a value after it.
+///
+/// ## InvalidValue { key, value, message, occurrence }
+///
+/// This variant is used internally by Jargon to warn when the value of a required argument
+/// fails to parse, with the occurrence of the key at fault if it takes several values.
+///
+/// ## InvalidChoice { key, value, choices }
+///
+/// This variant is used internally by Jargon to warn when the value of an argument is none of
+/// the ones it accepts.
+///
+/// ## `UnknownArgs(Vec<Unknown>)`
+///
+/// This variant is used internally by Jargon to warn when arguments that look like keys are
+/// left over after parsing.
+///
+/// ## UnknownSubcommand { arg, suggestion, expected }
+///
+/// This variant is used internally by Jargon to warn when none of the subcommands given to a
+/// `Dispatch` is in arguments, listing the ones it knows and the closest one, if any.
+///
+/// ## Ambiguous { arg, candidates }
+///
+/// This variant is used internally by Jargon to warn when an abbreviation could be more than
+/// one key or subcommand.
+///
+/// ## Conflict { first, second }
+///
+/// This variant is used internally by Jargon to warn when two keys of an exclusive group are
+/// given.
+///
+/// ## Requires { key, required }
+///
+/// This variant is used internally by Jargon to warn when a key is given without another key it
+/// requires.
+///
+/// ## RequiredUnless { key, unless }
+///
+/// This variant is used internally by Jargon to warn when a key is not given, and neither is any
+/// of the keys that would make it optional.
+///
+/// ## ResponseFile { file, line, message }
+///
+/// This variant is used internally by Jargon to warn when an `@file` argument can't be read or
+/// expanded, with the line at fault if there is one.
+///
+/// ## Config { file, line, message }
+///
+/// This variant is used internally by Jargon to warn when a config file can't be read or parsed,
+/// with the line at fault if there is one.
+///
+/// ## Help(String)
+///
+/// This variant is used internally by Jargon when help is asked for by a `#[jargon(help)]` field,
+/// with the help text to print.
+///
 /// ## Other(String)
 ///
 /// This variant is used internally by Jargon when converting other types of errors to itself.
@@ -22,6 +89,146 @@ pub enum Error {
     /// (from the `res_*` functions) does not exist.
     MissingArg(Key),
 
+    /// # MissingArgEnv { key, var }
+    ///
+    /// This variant is used internally by Jargon to warn when a required argument does not exist,
+    /// and neither does the environment variable it falls back to.
+    MissingArgEnv {
+        /// The missing key.
+        key: Key,
+        /// The environment variable the key falls back to.
+        var: String,
+    },
+
+    /// # MissingValue(Key)
+    ///
+    /// This variant is used internally by Jargon to warn when a required argument is given,
+    /// but without a value after it.
+    MissingValue(Key),
+
+    /// # InvalidValue { key, value, message, occurrence }
+    ///
+    /// This variant is used internally by Jargon to warn when the value of a required argument
+    /// fails to parse, with the occurrence of the key at fault if it takes several values.
+    InvalidValue {
+        /// The key the value was given to.
+        key: Key,
+        /// The value as it was given.
+        value: String,
+        /// The error from parsing the value.
+        message: String,
+        /// Which occurrence of the key the value was given to, counting from 1.
+        occurrence: Option<usize>,
+    },
+
+    /// # InvalidChoice { key, value, choices }
+    ///
+    /// This variant is used internally by Jargon to warn when the value of an argument is none of
+    /// the ones it accepts.
+    InvalidChoice {
+        /// The key the value was given to.
+        key: Key,
+        /// The value as it was given.
+        value: String,
+        /// The values the key accepts.
+        choices: Vec<String>,
+    },
+
+    /// # `UnknownArgs(Vec<Unknown>)`
+    ///
+    /// This variant is used internally by Jargon to warn when arguments that look like keys are
+    /// left over after parsing.
+    UnknownArgs(Vec<Unknown>),
+
+    /// # UnknownSubcommand { arg, suggestion, expected }
+    ///
+    /// This variant is used internally by Jargon to warn when none of the subcommands given to a
+    /// `Dispatch` is in arguments, listing the ones it knows and the closest one, if any.
+    UnknownSubcommand {
+        /// The argument given where a subcommand was expected, if there is one.
+        arg: Option<String>,
+        /// The subcommand closest to the argument, if one is close enough to be a likely typo.
+        suggestion: Option<String>,
+        /// The subcommands that were expected.
+        expected: Vec<Key>,
+    },
+
+    /// # Ambiguous { arg, candidates }
+    ///
+    /// This variant is used internally by Jargon to warn when an abbreviation could be more than
+    /// one key or subcommand.
+    Ambiguous {
+        /// The abbreviation as it was given.
+        arg: String,
+        /// Every key or subcommand it could be.
+        candidates: Vec<Key>,
+    },
+
+    /// # Conflict { first, second }
+    ///
+    /// This variant is used internally by Jargon to warn when two keys of an exclusive group are
+    /// given.
+    Conflict {
+        /// The first key of the group given, as it was typed.
+        first: String,
+        /// The next key of the group given, as it was typed.
+        second: String,
+    },
+
+    /// # Requires { key, required }
+    ///
+    /// This variant is used internally by Jargon to warn when a key is given without another key it
+    /// requires.
+    Requires {
+        /// The key given.
+        key: Key,
+        /// The key it requires, which is missing.
+        required: Key,
+    },
+
+    /// # RequiredUnless { key, unless }
+    ///
+    /// This variant is used internally by Jargon to warn when a key is not given, and neither is
+    /// any of the keys that would make it optional.
+    RequiredUnless {
+        /// The missing key.
+        key: Key,
+        /// The keys that would make it optional.
+        unless: Vec<Key>,
+    },
+
+    /// # ResponseFile { file, line, message }
+    ///
+    /// This variant is used internally by Jargon to warn when an `@file` argument can't be read or
+    /// expanded, with the line at fault if there is one.
+    ResponseFile {
+        /// The response file, as it was named.
+        file: String,
+        /// The line of the file at fault, counting from 1.
+        line: Option<usize>,
+        /// What went wrong.
+        message: String,
+    },
+
+    /// # Config { file, line, message }
+    ///
+    /// This variant is used internally by Jargon to warn when a config file can't be read or
+    /// parsed, with the line at fault if there is one.
+    Config {
+        /// The config file.
+        file: String,
+        /// The line of the file at fault, counting from 1.
+        line: Option<usize>,
+        /// What went wrong.
+        message: String,
+    },
+
+    /// # Help(String)
+    ///
+    /// This variant is used internally by Jargon when help is asked for by a `#[jargon(help)]`
+    /// field, with the help text to print.
+    Help(String),
+
     /// # Other(String)
     ///
     /// This variant is used internally by Jargon when converting other types of errors to itself.
@@ -38,8 +245,139 @@ impl Display for Error {
             "{}",
             match self {
                 Error::MissingArg(k) => format!("Missing argument: '{}'", k),
+                Error::MissingArgEnv { key, var } => format!(
+                    "Missing argument: '{}' or environment variable '{}'",
+                    key, var
+                ),
+                Error::MissingValue(k) => format!("Missing value for argument: '{}'", k),
+                Error::InvalidValue {
+                    key,
+                    value,
+                    message,
+                    occurrence: Some(n),
+                } => format!(
+                    "Invalid value '{}' for occurrence {} of '{}': {}",
+                    value, n, key, message
+                ),
+                Error::InvalidValue {
+                    key,
+                    value,
+                    message,
+                    occurrence: None,
+                } => format!(
+                    "Invalid value '{}' for argument '{}': {}",
+                    value, key, message
+                ),
+                Error::InvalidChoice {
+                    key,
+                    value,
+                    choices,
+                } => format!(
+                    "Invalid value '{}' for argument '{}', expected one of: {}",
+                    value,
+                    key,
+                    choices
+                        .iter()
+                        .map(|c| format!("'{}'", c))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                Error::UnknownArgs(v) => format!(
+                    "Unknown argument{}: {}",
+                    if v.len() == 1 { "" } else { "s" },
+                    v.iter()
+                        .map(Unknown::to_string)
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                Error::UnknownSubcommand {
+                    arg,
+                    suggestion,
+                    expected,
+                } => format!(
+                    "{}, expected one of: {}{}",
+                    match arg {
+                        Some(arg) => format!("Unknown subcommand: '{}'", arg),
+                        None => "Missing subcommand".to_string(),
+                    },
+                    expected
+                        .iter()
+                        .map(|k| format!("'{}'", k))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    match suggestion {
+                        Some(s) => format!("; did you mean '{}'?", s),
+                        None => String::new(),
+                    }
+                ),
+                Error::Ambiguous { arg, candidates } => format!(
+                    "Ambiguous argument: '{}' could be {}",
+                    arg,
+                    candidates
+                        .iter()
+                        .map(|k| format!("'{}'", k))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                Error::Conflict { first, second } => format!(
+                    "Conflicting arguments: '{}' cannot be used with '{}'",
+                    first, second
+                ),
+                Error::Requires { key, required } =>
+                    format!("Argument '{}' requires '{}'", key, required),
+                Error::RequiredUnless { key, unless } => format!(
+                    "Missing argument: '{}', required unless {} is given",
+                    key,
+                    unless
+                        .iter()
+                        .map(|k| format!("'{}'", k))
+                        .collect::<Vec<String>>()
+                        .join(" or ")
+                ),
+                Error::ResponseFile {
+                    file,
+                    line: Some(line),
+                    message,
+                } => format!("Response file '{}', line {}: {}", file, line, message),
+                Error::ResponseFile {
+                    file,
+                    line: None,
+                    message,
+                } => format!("Response file '{}': {}", file, message),
+                Error::Config {
+                    file,
+                    line: Some(line),
+                    message,
+                } => format!("Config file '{}', line {}: {}", file, line, message),
+                Error::Config {
+                    file,
+                    line: None,
+                    message,
+                } => format!("Config file '{}': {}", file, message),
+                Error::Help(help) => help.to_string(),
                 Error::Other(e) => e.to_string(),
             }
         )
     }
 }
+
+/// # Unknown
+///
+/// This struct is an argument that was not expected, with the key it was most likely meant to
+/// be, if any key is close.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Unknown {
+    /// The argument as it was given.
+    pub arg: String,
+    /// The closest key that was looked for.
+    pub suggestion: Option<String>,
+}
+
+impl Display for Unknown {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.suggestion {
+            Some(s) => write!(f, "'{}'; did you mean '{}'?", self.arg, s),
+            None => write!(f, "'{}'", self.arg),
+        }
+    }
+}
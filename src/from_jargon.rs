@@ -0,0 +1,99 @@
+use super::Error;
+use super::Jargon;
+use super::Spec;
+
+/// # FromJargon
+///
+/// This trait is for types built from the arguments in a Jargon. With the `derive` feature,
+/// `#[derive(Jargon)]` implements it for a struct with named fields, each field declaring one
+/// argument through the `#[jargon(...)]` attribute:
+///
+/// - `short` or `short = 'c'` gives the field a short key, by default its first letter.
+/// - `long` or `long = "name"` gives the field a long key, by default its name with `-` for `_`.
+///   A field without `short`, `long` or `positional` has the default long key.
+/// - `default = "value"` and `env = "VAR"` are used when the argument is not given.
+/// - `value = "NAME"` names the value in help text, by default the field's name in capitals.
+/// - `help = "text"` describes the argument, otherwise the field's doc comment does.
+/// - `count` counts a flag, for fields like `verbose: u8`.
+/// - `positional` takes the field from the arguments left over, in field order.
+/// - `help` makes a `bool` flag ask for help: when it is given, `from_jargon` returns
+///   Err(jargon_args::Error::Help) with the help text, before looking for required arguments.
+///
+/// `bool` fields are flags, `Option<T>` fields are optional, `Vec<T>` fields take every
+/// occurrence, and any other type is required. On the struct, `name`, `about` and `usage` set
+/// the help text, with the crate's name and the struct's doc comment used if they are missing.
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # fn main() -> Result<(), jargon_args::Error> {
+/// use jargon_args::{FromJargon, Jargon};
+///
+/// /// Print NAME with any leading directory components removed.
+/// #[derive(Jargon)]
+/// #[jargon(name = "basename")]
+/// struct Args {
+///     /// support multiple arguments
+///     #[jargon(short = 'a')]
+///     multiple: bool,
+///     /// remove a trailing SUFFIX
+///     #[jargon(short, long, value = "SUFFIX")]
+///     suffix: Option<String>,
+///     #[jargon(positional)]
+///     names: Vec<String>,
+/// }
+///
+/// let args: Args = Args::from_jargon(Jargon::from_vec(vec!["basename", "-a", "a/b", "c/d"]))?;
+/// assert!(args.multiple);
+/// assert_eq!(args.suffix, None);
+/// assert_eq!(args.names, vec!["a/b".to_string(), "c/d".to_string()]);
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "derive"))]
+/// # fn main() {}
+/// ```
+///
+/// A struct with required arguments can't be built from `--help` alone, so its help flag is
+/// marked with `help`:
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # fn main() {
+/// use jargon_args::{Error, FromJargon, Jargon};
+///
+/// #[derive(Jargon)]
+/// #[jargon(name = "cp")]
+/// struct Args {
+///     /// display this help and exit
+///     #[jargon(short, long, help)]
+///     help: bool,
+///     #[jargon(positional)]
+///     source: String,
+/// }
+///
+/// match Args::from_jargon(Jargon::from_vec(vec!["cp", "--help"])) {
+///     Err(Error::Help(help)) => assert_eq!(help, Args::help()),
+///     _ => unreachable!(),
+/// }
+/// # }
+/// # #[cfg(not(feature = "derive"))]
+/// # fn main() {}
+/// ```
+pub trait FromJargon: Sized {
+    /// Returns the spec declaring every argument of the type.
+    fn spec() -> Spec;
+
+    /// Builds the type out of provided Jargon.
+    /// Err(jargon_args::Error) if an argument is missing, fails to parse or is unknown, or
+    /// Err(jargon_args::Error::Help) if a `help` field is given.
+    fn from_jargon(jargon: Jargon) -> Result<Self, Error>;
+
+    /// Builds the type out of the program's arguments.
+    fn from_env() -> Result<Self, Error> {
+        Self::from_jargon(Jargon::from_env())
+    }
+
+    /// Renders the help text of the type's spec.
+    fn help() -> String {
+        Self::spec().help()
+    }
+}
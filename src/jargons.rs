@@ -1,5 +1,15 @@
+use super::choices::{choose, pick};
+use super::os;
+use super::Choices;
 use super::Error;
 use super::Key;
+use super::Source;
+use super::Unknown;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::fmt::Display;
+use std::path::PathBuf;
 use std::result::Result;
 use std::str::FromStr;
 
@@ -8,6 +18,9 @@ use std::str::FromStr;
 /// This is the main struct in this crate. This is what is used to handle arguments,
 /// and get arguments' values.
 ///
+/// Arguments are kept as `OsString`s, so they do not need to be valid UTF-8. Keys are matched
+/// against the UTF-8 part at the start of each argument.
+///
 /// # Example
 ///
 /// ```
@@ -20,73 +33,440 @@ use std::str::FromStr;
 /// }
 ///
 /// // ...
-#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
-pub struct Jargon(pub(crate) Vec<String>);
+/// ```
+///
+/// Two Jargons are equal when their remaining arguments are, whatever keys were looked for in
+/// them.
+#[derive(Debug, Clone)]
+pub struct Jargon(pub(crate) Vec<OsString>, pub(crate) State);
+
+/// What a Jargon remembers about how it was used.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct State {
+    /// Every key looked for, to suggest one when an argument is unknown.
+    pub(crate) queried: Vec<Key>,
+    /// Keys and the environment variables they fall back to.
+    pub(crate) env: Vec<(Key, String)>,
+    /// Variables used instead of the process's environment.
+    pub(crate) vars: Option<BTreeMap<String, OsString>>,
+    /// Defaults given for keys, to show them in help.
+    pub(crate) defaults: Vec<(Key, String)>,
+    /// Where the last value of each key came from.
+    pub(crate) sources: Vec<(Key, Source)>,
+    /// Whether subcommands only match the first argument that isn't a key.
+    pub(crate) positional: bool,
+    /// Other names of subcommands, with the subcommand each stands for.
+    pub(crate) aliases: Vec<(String, Key)>,
+    /// Known subcommands, if they can be abbreviated.
+    pub(crate) prefixes: Option<Vec<Key>>,
+    /// Known long keys, if they can be abbreviated.
+    pub(crate) long_prefixes: Option<Vec<Key>>,
+    /// Entries of the config files loaded, with the file each is from, in the order they were
+    /// read.
+    pub(crate) config: Vec<(String, String, PathBuf)>,
+    /// Relationships between keys, checked by `validate`.
+    pub(crate) constraints: Vec<Constraint>,
+    /// How each key found in arguments was typed, to name it in errors.
+    pub(crate) typed: Vec<(Key, String)>,
+    /// Keys known to take a value, whose short letter ends a cluster of short keys.
+    pub(crate) options: Vec<Key>,
+}
+
+/// A relationship between keys.
+#[derive(Debug, Clone)]
+pub(crate) enum Constraint {
+    /// The first key can only be given with the second.
+    Requires(Key, Key),
+    /// The key must be given unless one of the others is.
+    RequiredUnless(Key, Vec<Key>),
+    /// At most one of the keys can be given.
+    Exclusive(Vec<Key>),
+}
 
 impl Jargon {
-    /// Extracts a program's arguments from the environment.
+    /// Extracts a program's arguments from the environment. Arguments do not have to be valid
+    /// UTF-8.
     pub fn from_env() -> Self {
-        Self(std::env::args().collect())
+        Self(std::env::args_os().collect(), State::default())
     }
 
     /// Places provided vector into Jargon. Please have the program's name or subcommand's name at
     /// index `0`. 0 is always ignored.
     pub fn from_vec<T: ToString>(v: Vec<T>) -> Self {
-        Self(v.iter().map(|x| x.to_string()).collect())
+        Self(
+            v.iter().map(|x| x.to_string().into()).collect(),
+            State::default(),
+        )
+    }
+
+    /// Places provided vector of `OsString`s, `PathBuf`s or the like into Jargon. Please have the
+    /// program's name or subcommand's name at index `0`. 0 is always ignored.
+    pub fn from_vec_os<T: AsRef<OsStr>>(v: Vec<T>) -> Self {
+        Self(
+            v.iter().map(|x| x.as_ref().to_os_string()).collect(),
+            State::default(),
+        )
+    }
+
+    /// Binds provided key to an environment variable. When the key isn't in arguments, the
+    /// `option_arg` and `result_arg` families use the variable's value instead.
+    ///
+    /// ```
+    /// use jargon_args::Jargon;
+    /// let mut j: Jargon = Jargon::from_vec(vec!["deploy"]);
+    ///
+    /// j.bind_env("--region", "APP_REGION");
+    /// j.with_env(vec![("APP_REGION", "eu-west-1")]);
+    ///
+    /// assert_eq!(j.option_arg("--region"), Some("eu-west-1".to_string()));
+    /// ```
+    pub fn bind_env<K: Into<Key>, T: ToString>(&mut self, key: K, var: T) {
+        let key: Key = key.into();
+        self.1.env.retain(|(k, _)| *k != key);
+        self.1.env.push((key, var.to_string()));
+    }
+
+    /// Uses provided variables instead of the process's environment for keys bound with
+    /// `bind_env`, and to find config files with `config_files`. Handy for tests.
+    pub fn with_env<I, K, V>(&mut self, vars: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: ToString,
+        V: Into<OsString>,
+    {
+        self.1.vars = Some(
+            vars.into_iter()
+                .map(|(k, v)| (k.to_string(), v.into()))
+                .collect(),
+        );
     }
 
     /// Checks if provided key is given in arguments. Removes it.
+    ///
+    /// Long keys with an attached value, like `--key=value`, also match and are removed whole.
+    /// Short keys also match inside a cluster like `-xvf`, where only their letter is removed.
+    ///
+    /// A cluster ends at the first short key known to take a value, because the rest is its
+    /// value, so `-ofile.txt` doesn't give `-f` once `-o` was taken or declared with `options`.
+    ///
+    /// ```
+    /// let mut j = jargon_args::Jargon::from_vec(vec!["cc", "-ofile.txt", "-lv"]);
+    /// j.options(vec!["-o"]);
+    ///
+    /// assert!(!j.contains("-f"));
+    /// assert!(j.contains("-l"));
+    /// let out: Option<String> = j.option_arg("-o");
+    /// assert_eq!(out, Some("file.txt".to_string()));
+    /// ```
+    ///
+    /// A key that isn't in arguments is also given when a loaded config file sets it to `true`,
+    /// `yes`, `on` or a count above 0.
     pub fn contains<K: Into<Key>>(&mut self, key: K) -> bool {
         let key: Key = key.into();
-        let len: usize = self.0.len();
-
-        match key {
-            Key::Dual {
-                char: c,
-                s_txt: s,
-                l_txt: l,
-            } => {
-                let s: Key = Key::Short { char: c, txt: s };
-                let l: Key = Key::Long { char: c, txt: l };
-                for i in 0..len {
-                    let cont: Key = self.0[i].clone().into();
-                    if cont == s || cont == l {
-                        self.0.remove(i);
-                        return true;
-                    }
-                }
-            }
-            key => {
-                for i in 0..len {
-                    let cont: Key = self.0[i].clone().into();
-                    if cont == key {
-                        self.0.remove(i);
-                        return true;
-                    }
-                }
-            }
+        if self.remove(&key) {
+            self.record(&key, Source::CommandLine);
+            return true;
         }
+        self.configured_count(&key) > 0
+    }
 
-        false
+    /// Counts how many times provided key is given in arguments, like `-v -v --verbose` or
+    /// `-vvv`. Removes all of them.
+    ///
+    /// Like `contains`, clusters end at the first short key known to take a value.
+    ///
+    /// A key that isn't in arguments is counted from a loaded config file, as `verbose = 2` or
+    /// `verbose = true`.
+    pub fn count<K: Into<Key>>(&mut self, key: K) -> usize {
+        let key: Key = key.into();
+        let mut n: usize = 0;
+        while self.remove(&key) {
+            n += 1;
+        }
+        if n == 0 {
+            return self.configured_count(&key);
+        }
+        self.record(&key, Source::CommandLine);
+        n
     }
 
     #[cfg(feature = "no_mut")]
     /// Checks if provided key is given in arguments. Dose not remove it.
     pub fn contains_nomut<K: Into<Key>>(&self, key: K) -> bool {
-        let m = self.0.clone();
-        let mut m = Self(m);
+        let mut m = self.clone();
         m.contains(key)
     }
 
+    /// Makes subcommands match only the first argument before `--` that isn't a key, instead of
+    /// anywhere in arguments. Their functions then get the subcommand's name followed by exactly
+    /// the arguments after it, which are removed from this Jargon, so the options before it can
+    /// still be taken from here.
+    ///
+    /// Options before the subcommand whose values are separate arguments, like `--name list`,
+    /// must be taken before matching subcommands so their values are not matched instead.
+    ///
+    /// ```
+    /// let mut j = jargon_args::Jargon::from_vec(vec!["git", "-C", "list", "remote", "-v"]);
+    /// j.positional_subcommands();
+    ///
+    /// let dir: Option<String> = j.option_arg("-C");
+    /// assert_eq!(dir, Some("list".to_string()));
+    /// assert_eq!(j.subcommand("list"), None);
+    /// assert_eq!(
+    ///     j.subcommand("remote"),
+    ///     Some(vec!["remote".to_string(), "-v".to_string()])
+    /// );
+    /// assert!(j.finish().is_empty());
+    /// ```
+    pub fn positional_subcommands(&mut self) {
+        self.1.positional = true;
+    }
+
+    /// Gives subcommand key other names, so that `rm` and `del` can run the function of
+    /// `remove`. The subcommand's function gets the name that was given.
+    ///
+    /// ```
+    /// let mut j = jargon_args::Jargon::from_vec(vec!["todo", "rm", "milk"]);
+    /// j.aliases("remove", vec!["rm", "del"]);
+    ///
+    /// assert_eq!(
+    ///     j.subcommand("remove"),
+    ///     Some(vec!["rm".to_string(), "milk".to_string()])
+    /// );
+    /// ```
+    pub fn aliases<K, I, T>(&mut self, key: K, aliases: I)
+    where
+        K: Into<Key>,
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        let key: Key = key.into();
+        for alias in aliases {
+            self.1.aliases.push((alias.to_string(), key.clone()));
+        }
+    }
+
+    /// Lets subcommands be abbreviated to any prefix that only one of them starts with, so `inst`
+    /// runs the function of `install`. known is every subcommand of the program, so that
+    /// abbreviations are never matched before all of them are known. Subcommands looked for
+    /// later and aliases are known too.
+    ///
+    /// An abbreviation of more than one subcommand matches neither, and `res_on_subcommand` and
+    /// `Dispatch` return Err(jargon_args::Error::Ambiguous) listing them.
+    ///
+    /// ```
+    /// use jargon_args::{Error, Jargon};
+    ///
+    /// let mut j: Jargon = Jargon::from_vec(vec!["pkg", "inst", "jargon"]);
+    /// j.subcommand_prefixes(vec!["install", "list"]);
+    /// assert!(j.subcommand("install").is_some());
+    ///
+    /// let mut j: Jargon = Jargon::from_vec(vec!["pkg", "ins", "jargon"]);
+    /// j.subcommand_prefixes(vec!["install", "inspect"]);
+    /// assert_eq!(
+    ///     j.res_on_subcommand("install", Ok),
+    ///     Err(Error::Ambiguous {
+    ///         arg: "ins".to_string(),
+    ///         candidates: vec!["install".into(), "inspect".into()],
+    ///     })
+    /// );
+    /// ```
+    pub fn subcommand_prefixes<I, K>(&mut self, known: I)
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<Key>,
+    {
+        self.1.prefixes = Some(known.into_iter().map(Into::into).collect());
+    }
+
+    /// Lets long keys be abbreviated to any prefix that only one of them starts with, like
+    /// getopt_long, so `--verb` is `--verbose`. known is every long key of the program, or
+    /// Key::Dual with one, so that abbreviations are never matched before all of them are known.
+    /// Long keys looked for later are known too.
+    ///
+    /// An abbreviation of more than one long key matches neither, the `result_*` functions and
+    /// `finish_strict` return Err(jargon_args::Error::Ambiguous) listing them.
+    ///
+    /// ```
+    /// use jargon_args::{Error, Jargon};
+    ///
+    /// let mut j: Jargon = Jargon::from_vec(vec!["ls", "--al", "--col=never"]);
+    /// j.long_prefixes(vec!["--all", "--almost-all", "--color"]);
+    ///
+    /// assert_eq!(j.result_arg("--color"), Ok("never".to_string()));
+    /// assert!(!j.contains(["-a", "--all"]));
+    /// assert_eq!(
+    ///     j.finish_strict(),
+    ///     Err(Error::Ambiguous {
+    ///         arg: "--al".to_string(),
+    ///         candidates: vec!["--all".into(), "--almost-all".into()],
+    ///     })
+    /// );
+    /// ```
+    pub fn long_prefixes<I, K>(&mut self, known: I)
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<Key>,
+    {
+        self.1.long_prefixes = Some(known.into_iter().map(Into::into).collect());
+    }
+
+    /// Declares keys that take a value before they are taken, so checking for short flags first
+    /// does not match the letters of their attached values, like the `f` of `-ofile`. Keys are
+    /// known to take a value once taken anyway, and `Spec::parse` declares its options.
+    pub fn options<I, K>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<Key>,
+    {
+        for key in keys {
+            let key: Key = key.into();
+            if !self.takes_value(&key) {
+                self.1.options.push(key);
+            }
+        }
+    }
+
+    /// Declares that key can only be given with required, like `--tls-key` with `--tls`. Checked
+    /// by `validate` once arguments are parsed.
+    ///
+    /// ```
+    /// use jargon_args::{Error, Jargon};
+    ///
+    /// let mut j: Jargon = Jargon::from_vec(vec!["serve", "--tls-key", "key.pem"]);
+    /// j.requires("--tls-key", "--tls");
+    ///
+    /// let tls: bool = j.contains("--tls");
+    /// let key: Option<String> = j.option_arg("--tls-key");
+    /// assert_eq!(
+    ///     j.validate(),
+    ///     Err(Error::Requires {
+    ///         key: "--tls-key".into(),
+    ///         required: "--tls".into(),
+    ///     })
+    /// );
+    /// ```
+    pub fn requires<K: Into<Key>, R: Into<Key>>(&mut self, key: K, required: R) {
+        self.1
+            .constraints
+            .push(Constraint::Requires(key.into(), required.into()));
+    }
+
+    /// Declares that key must be given if other is, the other way around from `requires`.
+    /// Checked by `validate` once arguments are parsed.
+    pub fn required_if<K: Into<Key>, O: Into<Key>>(&mut self, key: K, other: O) {
+        self.requires(other, key);
+    }
+
+    /// Declares that key must be given unless one of others is, like `--output` unless
+    /// `--stdout`. Checked by `validate` once arguments are parsed.
+    ///
+    /// ```
+    /// use jargon_args::{Error, Jargon};
+    ///
+    /// let mut j: Jargon = Jargon::from_vec(vec!["render"]);
+    /// j.required_unless("--output", vec!["--stdout"]);
+    ///
+    /// let stdout: bool = j.contains("--stdout");
+    /// let output: Option<String> = j.option_arg("--output");
+    /// assert_eq!(
+    ///     j.validate(),
+    ///     Err(Error::RequiredUnless {
+    ///         key: "--output".into(),
+    ///         unless: vec!["--stdout".into()],
+    ///     })
+    /// );
+    /// ```
+    pub fn required_unless<K, I, O>(&mut self, key: K, others: I)
+    where
+        K: Into<Key>,
+        I: IntoIterator<Item = O>,
+        O: Into<Key>,
+    {
+        self.1.constraints.push(Constraint::RequiredUnless(
+            key.into(),
+            others.into_iter().map(Into::into).collect(),
+        ));
+    }
+
+    /// Checks the relationships declared with `requires`, `required_if`, `required_unless` and
+    /// `exclusive`, in the order they were declared. A key counts as given once it was found, in
+    /// arguments, its environment variable or a config file, so call this after taking every
+    /// key. `finish_strict` calls it too.
+    ///
+    /// Err(jargon_args::Error::Requires), Err(jargon_args::Error::RequiredUnless) or
+    /// Err(jargon_args::Error::Conflict) for the first one broken.
+    pub fn validate(&self) -> Result<(), Error> {
+        for constraint in &self.1.constraints {
+            match constraint {
+                Constraint::Requires(key, required) if self.given(key) && !self.given(required) => {
+                    return Err(Error::Requires {
+                        key: key.clone(),
+                        required: required.clone(),
+                    })
+                }
+                Constraint::RequiredUnless(key, unless)
+                    if !self.given(key) && !unless.iter().any(|k| self.given(k)) =>
+                {
+                    return Err(Error::RequiredUnless {
+                        key: key.clone(),
+                        unless: unless.clone(),
+                    })
+                }
+                Constraint::Exclusive(group) => {
+                    let mut given = group.iter().filter(|k| self.given(k));
+                    if let (Some(first), Some(second)) = (given.next(), given.next()) {
+                        return Err(Error::Conflict {
+                            first: self.typed(first),
+                            second: self.typed(second),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Declares that at most one key of group can be given, like one of `--json`, `--yaml` and
+    /// `--table`. Checked by `validate` once arguments are parsed, which returns
+    /// Err(jargon_args::Error::Conflict) naming the first two of group given, as they were typed.
+    ///
+    /// ```
+    /// use jargon_args::{Error, Jargon};
+    ///
+    /// let mut j: Jargon = Jargon::from_vec(vec!["report", "--yaml", "-t"]);
+    /// j.exclusive(vec!["--json", "--yaml", "-t"]);
+    ///
+    /// let json: bool = j.contains("--json");
+    /// let yaml: bool = j.contains("--yaml");
+    /// let table: bool = j.contains("-t");
+    /// assert_eq!(
+    ///     j.validate(),
+    ///     Err(Error::Conflict {
+    ///         first: "--yaml".to_string(),
+    ///         second: "-t".to_string(),
+    ///     })
+    /// );
+    /// ```
+    pub fn exclusive<I, K>(&mut self, group: I)
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<Key>,
+    {
+        self.1.constraints.push(Constraint::Exclusive(
+            group.into_iter().map(Into::into).collect(),
+        ));
+    }
+
     /// Runs function that does not return a value if specified key exists.
-    /// Removes the program's name from provided vector.
+    /// Removes the program's name from provided vector. Arguments after `--` are passed on as is,
+    /// `--` included.
     pub fn on_subcommand<K: Into<Key>, F: FnMut(Vec<String>)>(&mut self, key: K, mut f: F) {
         let key: Key = key.into();
-        for i in 0..self.0.len() {
-            let cont: Key = self.0[i].clone().into();
-            if cont.is_sub() && cont == key {
-                return f(self.clone().finish());
-            }
+        self.query(&key);
+        if let Ok(Some(args)) = self.enter(&key) {
+            f(args)
         }
     }
 
@@ -98,11 +478,9 @@ impl Jargon {
         mut f: F,
     ) -> Option<T> {
         let key: Key = key.into();
-        for i in 0..self.0.len() {
-            let cont: Key = self.0[i].clone().into();
-            if cont.is_sub() && cont == key {
-                return f(self.clone().finish());
-            }
+        self.query(&key);
+        if let Ok(Some(args)) = self.enter(&key) {
+            return f(args);
         }
         None
     }
@@ -115,11 +493,9 @@ impl Jargon {
         mut f: F,
     ) -> Result<T, Error> {
         let key: Key = key.into();
-        for i in 0..self.0.len() {
-            let cont: Key = self.0[i].clone().into();
-            if cont.is_sub() && cont == key {
-                return f(self.clone().finish());
-            }
+        self.query(&key);
+        if let Some(args) = self.enter(&key)? {
+            return f(args);
         }
 
         Err(Error::MissingArg(key))
@@ -128,13 +504,7 @@ impl Jargon {
     /// Checks if key exists, removes it, and returns it and all remaining arguments in
     /// Some(Vec<String>). None if key isn't in arguments.
     pub fn subcommand<K: Into<Key>>(&mut self, key: K) -> Option<Vec<String>> {
-        let mut v: Vec<String> = Vec::new();
-        self.on_subcommand(key, |vv| v = vv);
-        if v.is_empty() {
-            None
-        } else {
-            Some(v)
-        }
+        self.opt_on_subcommand(key, Some)
     }
 
     #[cfg(feature = "no_mut")]
@@ -142,70 +512,843 @@ impl Jargon {
     /// and returns it and all remaining arguments in
     /// Some(Vec<String>). None if key isn't in arguments.
     pub fn subcommand_nomut<K: Into<Key>>(&self, key: K) -> Option<Vec<String>> {
-        Jargon::from_vec(self.0.clone()).subcommand(key)
+        self.clone().subcommand(key)
     }
 
     /// Checks for provided key in arguments, removes it, returns Some(String) with the value after it if there is one.
     /// None is there is no value.
+    ///
+    /// The value may also be attached to the key, as in `--key=value` or `-kvalue`. When the key
+    /// is in a cluster of short keys, like `-xvf`, the rest of the cluster after it is the value,
+    /// or the next argument if it is last. From then on key is known to take a value, so
+    /// `contains` and `count` never match letters of its value.
     pub fn option_arg<T: FromStr, K: Into<Key>>(&mut self, key: K) -> Option<T> {
+        self.value(&key.into())
+            .ok()?
+            .into_string()
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    /// Works like `option_arg`, but returns default if there is no value or it fails to parse.
+    /// The default is remembered for `defaults`, and `source` tells which one was used.
+    ///
+    /// A value that fails to parse is dropped silently, though `source` still tells where it
+    /// came from. Use `result_arg_or` to report it instead.
+    pub fn option_arg_or<T: FromStr + ToString, K: Into<Key>>(&mut self, key: K, default: T) -> T {
         let key: Key = key.into();
-        let len: usize = self.0.len();
-
-        match key {
-            Key::Dual {
-                char: c,
-                s_txt: s,
-                l_txt: l,
-            } => {
-                let s: Key = Key::Short { char: c, txt: s };
-                let l: Key = Key::Long { char: c, txt: l };
-                for i in 0..len {
-                    let cont: Key = self.0[i].clone().into();
-                    if cont == s || cont == l {
-                        if i >= self.0.len() - 1 {
-                            return None;
-                        }
-                        return if !self.0[i + 1].starts_with(s.char())
-                            || !self.0[i + 1].starts_with(l.char())
-                        {
-                            self.0.remove(i);
-                            self.0.remove(i).parse().ok()
-                        } else {
-                            None
-                        };
-                    }
+        self.record_default(&key, default.to_string());
+        self.lookup(&key).unwrap_or(default)
+    }
+
+    /// Works like `option_arg_or`, but the default is computed by f, only when it is needed.
+    /// It is remembered for `defaults` once computed.
+    pub fn option_arg_or_else<T, K, F>(&mut self, key: K, f: F) -> T
+    where
+        T: FromStr + ToString,
+        K: Into<Key>,
+        F: FnOnce() -> T,
+    {
+        let key: Key = key.into();
+        self.lookup(&key).unwrap_or_else(|| {
+            let default: T = f();
+            self.record_default(&key, default.to_string());
+            default
+        })
+    }
+
+    /// Works like `option_arg_or`, but returns Err(jargon_args::Error) if key is given without
+    /// a value, `MissingValue`, or with one that fails to parse, `InvalidValue`. The default is
+    /// only used when key isn't given at all.
+    ///
+    /// ```
+    /// use jargon_args::{Error, Jargon};
+    ///
+    /// let mut j: Jargon = Jargon::from_vec(vec!["serve", "--port", "abc"]);
+    ///
+    /// assert_eq!(j.result_arg_or("--host", "localhost".to_string()), Ok("localhost".to_string()));
+    /// assert_eq!(
+    ///     j.result_arg_or("--port", 80u16),
+    ///     Err(Error::InvalidValue {
+    ///         key: "--port".into(),
+    ///         value: "abc".to_string(),
+    ///         message: "invalid digit found in string".to_string(),
+    ///         occurrence: None,
+    ///     })
+    /// );
+    /// ```
+    pub fn result_arg_or<T, K>(&mut self, key: K, default: T) -> Result<T, Error>
+    where
+        T: FromStr + ToString,
+        T::Err: Display,
+        K: Into<Key>,
+    {
+        let key: Key = key.into();
+        self.record_default(&key, default.to_string());
+        match self.value(&key) {
+            Ok(value) => parse(&key, value),
+            Err(Error::MissingArg(_)) | Err(Error::MissingArgEnv { .. }) => {
+                self.record(&key, Source::Default);
+                Ok(default)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns where the last value looked up for provided key came from. None if it was never
+    /// found.
+    pub fn source<K: Into<Key>>(&self, key: K) -> Option<Source> {
+        let key: Key = key.into();
+        self.1
+            .sources
+            .iter()
+            .find(|(k, _)| k.covers(&key) || key.covers(k))
+            .map(|(_, source)| source.clone())
+    }
+
+    /// Returns the defaults given to `option_arg_or` and `option_arg_or_else`, so help can show
+    /// them. See `Spec::defaults`.
+    pub fn defaults(&self) -> Vec<(Key, String)> {
+        self.1.defaults.clone()
+    }
+
+    /// Checks for provided key in arguments, removes it, returns Ok(String) with the value after it if there is one.
+    /// Err(jargon_args::Error) is there is no value: `MissingArg` if the key isn't in arguments,
+    /// `MissingArgEnv` if its environment variable isn't set either, `MissingValue` if nothing
+    /// follows it, and `InvalidValue` if the value fails to parse.
+    ///
+    /// Since 0.3.0, T's parse error must implement `Display`, so `InvalidValue` can say why the
+    /// value failed. Types whose error doesn't can be taken with `result_arg_os` and parsed by
+    /// hand.
+    pub fn result_arg<T: FromStr, K: Into<Key>>(&mut self, key: K) -> Result<T, Error>
+    where
+        T::Err: Display,
+    {
+        let key: Key = key.into();
+        let value: OsString = self.value(&key)?;
+        parse(&key, value)
+    }
+
+    /// Works like `option_arg`, but for a type that declares its spellings with
+    /// `jargon_args::Choices`. None if there is no value or it is none of them.
+    pub fn option_choice<T: Choices, K: Into<Key>>(&mut self, key: K) -> Option<T> {
+        self.result_choice(key).ok()
+    }
+
+    /// Works like `result_arg`, but for a type that declares its spellings with
+    /// `jargon_args::Choices`. Err(jargon_args::Error::InvalidChoice) listing them if the value
+    /// is none of them.
+    pub fn result_choice<T: Choices, K: Into<Key>>(&mut self, key: K) -> Result<T, Error> {
+        let key: Key = key.into();
+        let value: OsString = self.value(&key)?;
+        choose(&key, value)
+    }
+
+    /// Works like `result_arg`, but the value must be one of choices, and is returned as it is
+    /// spelled there. With ignore_case, choices match whatever their case.
+    /// Err(jargon_args::Error::InvalidChoice) listing them if the value is none of them.
+    ///
+    /// ```
+    /// let mut j = jargon_args::Jargon::from_vec(vec!["ls", "--color", "Always"]);
+    ///
+    /// assert_eq!(
+    ///     j.result_arg_choice("--color", &["auto", "always", "never"], true),
+    ///     Ok("always".to_string())
+    /// );
+    /// ```
+    pub fn result_arg_choice<K: Into<Key>, S: AsRef<str>>(
+        &mut self,
+        key: K,
+        choices: &[S],
+        ignore_case: bool,
+    ) -> Result<String, Error> {
+        let key: Key = key.into();
+        let value: OsString = self.value(&key)?;
+        let i: usize = pick(&key, value, choices, ignore_case)?;
+        Ok(choices[i].as_ref().to_string())
+    }
+
+    /// Checks for every occurrence of provided key in arguments, like `-I a -I b`, removes them,
+    /// and returns their values in order. Values that fail to parse are skipped.
+    pub fn option_args<T: FromStr, K: Into<Key>>(&mut self, key: K) -> Vec<T> {
+        let key: Key = key.into();
+        let mut values: Vec<OsString> = self.take_all(&key);
+        if values.is_empty() {
+            values.extend(self.value(&key).ok());
+        } else {
+            self.record(&key, Source::CommandLine);
+        }
+
+        values
+            .into_iter()
+            .filter_map(|v| v.into_string().ok()?.parse().ok())
+            .collect()
+    }
+
+    /// Checks for every occurrence of provided key in arguments, removes them, and returns
+    /// `Ok(Vec<T>)` with their values in order.
+    /// Err(jargon_args::Error) if there is none, or for the first occurrence without a value or
+    /// with one that fails to parse, naming which occurrence it was.
+    pub fn result_args<T: FromStr, K: Into<Key>>(&mut self, key: K) -> Result<Vec<T>, Error>
+    where
+        T::Err: Display,
+    {
+        let key: Key = key.into();
+        let mut values: Vec<T> = Vec::new();
+
+        loop {
+            match self.take(&key) {
+                Ok(value) => values.push(parse_occurrence(&key, value, Some(values.len() + 1))?),
+                Err(Error::MissingArg(_)) if !values.is_empty() => {
+                    self.record(&key, Source::CommandLine);
+                    return Ok(values);
                 }
+                Err(Error::MissingArg(_)) => return Ok(vec![parse(&key, self.value(&key)?)?]),
+                Err(e) => return Err(e),
             }
-            key => {
-                for i in 0..len {
-                    let cont: Key = self.0[i].clone().into();
-                    if cont == key {
-                        if i >= self.0.len() - 1 {
-                            return None;
-                        }
-                        return if !self.0[i + 1].starts_with(key.char()) {
-                            self.0.remove(i);
-                            self.0.remove(i).parse().ok()
-                        } else {
-                            None
-                        };
+        }
+    }
+
+    /// Works like `option_arg`, but returns the value as an `OsString`, `PathBuf` or anything
+    /// else made from an `OsString`, so it does not need to be valid UTF-8.
+    pub fn option_arg_os<T: From<OsString>, K: Into<Key>>(&mut self, key: K) -> Option<T> {
+        self.value(&key.into()).ok().map(T::from)
+    }
+
+    /// Works like `result_arg`, but returns the value as an `OsString`, `PathBuf` or anything
+    /// else made from an `OsString`, so it does not need to be valid UTF-8.
+    pub fn result_arg_os<T: From<OsString>, K: Into<Key>>(&mut self, key: K) -> Result<T, Error> {
+        self.value(&key.into()).map(T::from)
+    }
+
+    /// Drops your jargon instance and returns all remaining arguments. Arguments after the first
+    /// `--` are returned as they were given, without the `--`.
+    ///
+    /// Arguments that are not valid UTF-8 are converted lossily, use `finish_os` to keep them.
+    pub fn finish(self) -> Vec<String> {
+        self.finish_os::<OsString>()
+            .into_iter()
+            .map(|s| s.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// Works like `finish`, but returns the arguments as `OsString`s, `PathBuf`s or anything else
+    /// made from an `OsString`.
+    pub fn finish_os<T: From<OsString>>(self) -> Vec<T> {
+        let end: usize = self.end();
+        self.0
+            .into_iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(i, _)| *i != end)
+            .map(|(_, s)| T::from(s))
+            .collect()
+    }
+
+    /// Works like `finish`, but returns Err(jargon_args::Error) listing every remaining argument
+    /// before `--` that looks like a key, so misspelled keys are not taken for other arguments.
+    /// `-` alone is not a key.
+    ///
+    /// Each unknown argument comes with the closest key that was looked for, if one is close
+    /// enough to be a likely typo. Arguments that aren't keys are never unknown, so a subcommand
+    /// that is required should be matched with `dispatch`, which reports it.
+    ///
+    /// When every argument is known, the relationships between keys are checked with `validate`.
+    pub fn finish_strict(self) -> Result<Vec<String>, Error> {
+        let mut unknown: Vec<Unknown> = Vec::new();
+
+        for i in 1..self.end() {
+            let arg = self.text(i);
+            let candidates: Vec<Key> = self.expansions(&arg);
+            if candidates.len() > 1 {
+                return Err(Error::Ambiguous {
+                    arg: arg.split('=').next().unwrap_or("").to_string(),
+                    candidates,
+                });
+            }
+            if arg.starts_with('-') && arg.len() > 1 {
+                unknown.push(self.unknown(i, arg.split('=').next().unwrap_or(""), false));
+            }
+        }
+
+        if !unknown.is_empty() {
+            return Err(Error::UnknownArgs(unknown));
+        }
+        self.validate()?;
+        Ok(self.finish())
+    }
+
+    /// Remembers that key was looked for.
+    pub(crate) fn query(&mut self, key: &Key) {
+        if !self.1.queried.contains(key) {
+            self.1.queried.push(key.clone());
+        }
+    }
+
+    /// Describes the unknown argument at index i, suggesting the queried key closest to text.
+    /// Only subcommands are suggested for subcommands, and only other keys for other keys.
+    pub(crate) fn unknown(&self, i: usize, text: &str, sub: bool) -> Unknown {
+        let suggestion: Option<String> = self
+            .1
+            .queried
+            .iter()
+            .filter(|k| k.is_sub() == sub)
+            .flat_map(Key::spellings)
+            .map(|k| (distance(text, &k), k))
+            .filter(|(d, k)| {
+                let len: usize = k.trim_start_matches(|c: char| !c.is_alphanumeric()).len();
+                len > 1 && *d <= (len / 3).max(1)
+            })
+            .min()
+            .map(|(_, k)| k);
+
+        Unknown {
+            arg: self.0[i].to_string_lossy().into_owned(),
+            suggestion,
+        }
+    }
+
+    /// Returns the index of subcommand key in arguments. In positional mode, it can only be the
+    /// first argument that isn't a key.
+    /// Err(jargon_args::Error) if it is given as an abbreviation of more than one subcommand.
+    pub(crate) fn subcommand_index(&self, key: &Key) -> Result<Option<usize>, Error> {
+        if !key.is_sub() {
+            return Ok(None);
+        }
+
+        let indexes: Vec<usize> = match self.positional() {
+            Some((i, _)) if self.1.positional => vec![i],
+            _ if self.1.positional => Vec::new(),
+            _ => (1..self.end()).collect(),
+        };
+        for i in indexes {
+            if let Some(arg) = self.0[i].to_str() {
+                if self.names(arg, key)? {
+                    return Ok(Some(i));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Checks if arg is subcommand key, one of its aliases or, if subcommands can be
+    /// abbreviated, a prefix of only its names.
+    pub(crate) fn names(&self, arg: &str, key: &Key) -> Result<bool, Error> {
+        let text: String = key.text();
+        if arg == text || self.1.aliases.contains(&(arg.to_string(), key.clone())) {
+            return Ok(true);
+        }
+
+        let prefixes: &Vec<Key> = match &self.1.prefixes {
+            Some(prefixes) if !arg.is_empty() => prefixes,
+            _ => return Ok(false),
+        };
+        let known: Vec<(String, Key)> = prefixes
+            .iter()
+            .chain(self.1.queried.iter())
+            .filter(|k| k.is_sub())
+            .map(|k| (k.text(), k.clone()))
+            .chain(self.1.aliases.iter().cloned())
+            .collect();
+        if known.iter().any(|(name, _)| name == arg) {
+            return Ok(false);
+        }
+
+        let mut candidates: Vec<Key> = Vec::new();
+        for (_, k) in known.into_iter().filter(|(name, _)| name.starts_with(arg)) {
+            if !candidates.contains(&k) {
+                candidates.push(k);
+            }
+        }
+
+        match candidates.len() {
+            _ if !candidates.contains(key) => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::Ambiguous {
+                arg: arg.to_string(),
+                candidates,
+            }),
+        }
+    }
+
+    /// Returns the arguments for the function of subcommand key if it is given. In positional
+    /// mode, they are split off from this Jargon.
+    /// Err(jargon_args::Error) if it is given as an abbreviation of more than one subcommand.
+    pub(crate) fn enter(&mut self, key: &Key) -> Result<Option<Vec<String>>, Error> {
+        Ok(self.subcommand_index(key)?.map(|i| self.split(i)))
+    }
+
+    /// Enters the subcommand at index i. Returns the arguments its function gets, which are
+    /// removed from arguments in positional mode.
+    pub(crate) fn split(&mut self, i: usize) -> Vec<String> {
+        if !self.1.positional {
+            return self.rest();
+        }
+
+        self.0
+            .split_off(i)
+            .iter()
+            .map(|s| s.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// Returns all arguments except the program's name, untouched.
+    fn rest(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .skip(1)
+            .map(|s| s.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// Returns the UTF-8 part at the start of the argument at index i.
+    fn text(&self, i: usize) -> std::borrow::Cow<'_, str> {
+        os::utf8(&self.0[i])
+    }
+
+    /// Returns the index of the first `--`, where options end, or the number of arguments.
+    fn end(&self) -> usize {
+        (1..self.0.len())
+            .find(|i| self.0[*i] == "--")
+            .unwrap_or(self.0.len())
+    }
+
+    /// Finds the first argument matching key before `--`, and where in it the key is.
+    fn find(&self, key: &Key) -> Option<(usize, Spot)> {
+        (1..self.end()).find_map(|i| {
+            let whole: bool = self.0[i].to_str().is_some();
+            let text = self.text(i);
+            spot(&text, whole, key, &self.1.options)
+                .or_else(|| self.abbreviated(&text, whole, key).ok().flatten())
+                .map(|at| (i, at))
+        })
+    }
+
+    /// Matches arg as an abbreviation of the long key of key, if long keys can be abbreviated.
+    /// Err(jargon_args::Error) if it could also be another long key.
+    fn abbreviated(&self, arg: &str, whole: bool, key: &Key) -> Result<Option<Spot>, Error> {
+        let long: Key = match key.long() {
+            Some(long) => long,
+            None => return Ok(None),
+        };
+        let candidates: Vec<Key> = self.expansions(arg);
+        if !candidates.contains(&long) {
+            return Ok(None);
+        }
+
+        let name: &str = arg.split('=').next().unwrap_or("");
+        if candidates.len() > 1 {
+            Err(Error::Ambiguous {
+                arg: name.to_string(),
+                candidates,
+            })
+        } else if name.len() < arg.len() {
+            Ok(Some(Spot::Attached(name.len() + 1)))
+        } else if whole {
+            Ok(Some(Spot::Whole))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns every known long key arg is an abbreviation of, if long keys can be abbreviated.
+    /// Empty if arg is a whole long key.
+    fn expansions(&self, arg: &str) -> Vec<Key> {
+        let known: &Vec<Key> = match &self.1.long_prefixes {
+            Some(known) => known,
+            None => return Vec::new(),
+        };
+        let c: char = match arg.chars().next() {
+            Some(c) => c,
+            None => return Vec::new(),
+        };
+        let name: &str = match arg.split('=').next().unwrap_or("").strip_prefix(c) {
+            Some(name) => match name.strip_prefix(c) {
+                Some(name) if !name.is_empty() => name,
+                _ => return Vec::new(),
+            },
+            None => return Vec::new(),
+        };
+
+        let longs: Vec<(String, Key)> = known
+            .iter()
+            .chain(self.1.queried.iter())
+            .filter_map(Key::long)
+            .filter(|k| k.char() == c)
+            .map(|k| (k.text(), k))
+            .collect();
+        if longs.iter().any(|(text, _)| text == name) {
+            return Vec::new();
+        }
+
+        let mut candidates: Vec<Key> = Vec::new();
+        for (_, k) in longs.into_iter().filter(|(text, _)| text.starts_with(name)) {
+            if !candidates.contains(&k) {
+                candidates.push(k);
+            }
+        }
+        candidates
+    }
+
+    /// Returns the error for an ambiguous abbreviation that could be key, if one is given.
+    fn ambiguity(&self, key: &Key) -> Option<Error> {
+        (1..self.end()).find_map(|i| {
+            let whole: bool = self.0[i].to_str().is_some();
+            self.abbreviated(&self.text(i), whole, key).err()
+        })
+    }
+
+    /// Returns the index and text of the first argument before `--` that isn't a key.
+    pub(crate) fn positional(&self) -> Option<(usize, String)> {
+        (1..self.end())
+            .map(|i| (i, self.text(i).into_owned()))
+            .find(|(_, arg)| !arg.starts_with('-') || arg.len() == 1)
+    }
+
+    /// Returns the index of the first argument matching key, and the offset of key in it.
+    pub(crate) fn position(&self, key: &Key) -> Option<(usize, usize)> {
+        self.find(key).map(|(i, spot)| match spot {
+            Spot::Whole | Spot::Attached(_) => (i, 0),
+            Spot::Cluster(at) => (i, at),
+        })
+    }
+
+    /// Removes provided key from arguments, returns whether it was there.
+    fn remove(&mut self, key: &Key) -> bool {
+        self.query(key);
+        let (i, spot) = match self.find(key) {
+            Some(found) => found,
+            None => return false,
+        };
+        self.record_typed(key, i, spot);
+
+        if let Spot::Cluster(at) = spot {
+            let len: usize = self.text(i)[at..].chars().next().map_or(0, char::len_utf8);
+            let rest: OsString = os::split_off(&mut self.0[i], at + len);
+            os::split_off(&mut self.0[i], at);
+            if !self.takes_value(key) {
+                self.0[i].push(rest);
+            }
+            if self.0[i].len() <= key.char().len_utf8() {
+                self.0.remove(i);
+            }
+        } else {
+            self.0.remove(i);
+        }
+        true
+    }
+
+    /// Finds key and removes it and its value. Returns the value, `MissingArg` if key isn't in
+    /// arguments, or `MissingValue` if it has no value, in which case it is left in place.
+    pub(crate) fn take(&mut self, key: &Key) -> Result<OsString, Error> {
+        self.query(key);
+        if !self.takes_value(key) {
+            self.1.options.push(key.clone());
+        }
+        let (i, spot) = match self.find(key) {
+            Some(found) => found,
+            None => {
+                return Err(self
+                    .ambiguity(key)
+                    .unwrap_or_else(|| Error::MissingArg(key.clone())))
+            }
+        };
+        self.record_typed(key, i, spot);
+        let at: usize = match spot {
+            Spot::Whole => 0,
+            Spot::Attached(at) => return Ok(os::split_off(&mut self.0.remove(i), at)),
+            Spot::Cluster(at) => {
+                let len: usize = self.text(i)[at..].chars().next().map_or(0, char::len_utf8);
+                if at + len < self.0[i].len() {
+                    let value: OsString = os::split_off(&mut self.0[i], at + len);
+                    os::split_off(&mut self.0[i], at);
+                    if self.0[i].len() <= key.char().len_utf8() {
+                        self.0.remove(i);
                     }
+                    return Ok(value);
                 }
+                at
             }
+        };
+
+        if i + 1 >= self.end() || self.text(i + 1).starts_with(key.char()) {
+            return Err(Error::MissingValue(key.clone()));
+        }
+        if at == 0 {
+            self.0.remove(i);
+            Ok(self.0.remove(i))
+        } else {
+            os::split_off(&mut self.0[i], at);
+            Ok(self.0.remove(i + 1))
         }
+    }
 
-        None
+    /// Takes the value of key from arguments, or from its environment variable if it isn't in
+    /// arguments.
+    fn value(&mut self, key: &Key) -> Result<OsString, Error> {
+        let (value, source) = match self.take(key) {
+            Err(Error::MissingArg(_)) => self.fallback(key)?,
+            value => (value?, Source::CommandLine),
+        };
+        self.record(key, source);
+        Ok(value)
     }
 
-    /// Checks for provided key in arguments, removes it, returns Ok(String) with the value after it if there is one.
-    /// Err(jargon_args::Error) is there is no value.
-    pub fn result_arg<T: FromStr, K: Into<Key>>(&mut self, key: K) -> Result<T, Error> {
-        let key: Key = key.into();
-        self.option_arg(key.clone()).ok_or(Error::MissingArg(key))
+    /// Returns the value of the environment variable bound to key, or else of key in the loaded
+    /// config files. `MissingArgEnv` if neither is set and the variable is bound, or `MissingArg`.
+    pub(crate) fn fallback(&self, key: &Key) -> Result<(OsString, Source), Error> {
+        self.environment(key)
+            .or_else(|e| match self.configured(key) {
+                Some((value, file)) => Ok((value.into(), Source::Config(file))),
+                None => Err(e),
+            })
     }
 
-    /// Drops your jargon instance and returns all remaining arguments.
-    pub fn finish(self) -> Vec<String> {
-        self.0.iter().skip(1).map(|s| s.to_string()).collect()
+    /// Returns the value of the environment variable bound to key. `MissingArgEnv` if it isn't
+    /// set, or `MissingArg` if key isn't bound.
+    fn environment(&self, key: &Key) -> Result<(OsString, Source), Error> {
+        let (_, var) = self
+            .1
+            .env
+            .iter()
+            .find(|(k, _)| k.covers(key) || key.covers(k))
+            .ok_or_else(|| Error::MissingArg(key.clone()))?;
+
+        match self.var(var) {
+            Some(value) => Ok((value, Source::Environment(var.clone()))),
+            None => Err(Error::MissingArgEnv {
+                key: key.clone(),
+                var: var.clone(),
+            }),
+        }
+    }
+
+    /// Whether key was found, in arguments or elsewhere, rather than defaulted.
+    fn given(&self, key: &Key) -> bool {
+        !matches!(self.source(key.clone()), None | Some(Source::Default))
+    }
+
+    /// Returns the value of an environment variable, from the variables given to `with_env` if
+    /// there are any.
+    pub(crate) fn var(&self, var: &str) -> Option<OsString> {
+        match &self.1.vars {
+            Some(vars) => vars.get(var).cloned(),
+            None => std::env::var_os(var),
+        }
+    }
+
+    /// Remembers where the value of key came from.
+    pub(crate) fn record(&mut self, key: &Key, source: Source) {
+        self.1.sources.retain(|(k, _)| k != key);
+        self.1.sources.push((key.clone(), source));
+    }
+
+    /// Whether key is known to take a value.
+    fn takes_value(&self, key: &Key) -> bool {
+        self.1
+            .options
+            .iter()
+            .any(|k| k.covers(key) || key.covers(k))
+    }
+
+    /// Returns the value of key if it parses. Records that its default is used if key has no
+    /// value, and leaves the source of a value that fails to parse as it is.
+    fn lookup<T: FromStr>(&mut self, key: &Key) -> Option<T> {
+        match self.value(key) {
+            Ok(value) => value.into_string().ok()?.parse().ok(),
+            Err(_) => {
+                self.record(key, Source::Default);
+                None
+            }
+        }
+    }
+
+    /// Remembers how key was typed where it was found, at index i.
+    fn record_typed(&mut self, key: &Key, i: usize, spot: Spot) {
+        let text = self.text(i);
+        let typed: String = match spot {
+            Spot::Whole => text.into_owned(),
+            Spot::Attached(at) => text[..at - 1].to_string(),
+            Spot::Cluster(at) => format!(
+                "{}{}",
+                key.char(),
+                text[at..].chars().next().unwrap_or_default()
+            ),
+        };
+        self.1.typed.retain(|(k, _)| k != key);
+        self.1.typed.push((key.clone(), typed));
+    }
+
+    /// Returns how key was typed in arguments, or its long spelling if it came from elsewhere.
+    fn typed(&self, key: &Key) -> String {
+        match self.source(key.clone()) {
+            Some(Source::CommandLine) => self
+                .1
+                .typed
+                .iter()
+                .find(|(k, _)| k.covers(key) || key.covers(k))
+                .map(|(_, typed)| typed.clone()),
+            _ => None,
+        }
+        .unwrap_or_else(|| key.spellings().pop().unwrap_or_default())
+    }
+
+    /// Remembers the default of key.
+    fn record_default(&mut self, key: &Key, default: String) {
+        self.1.defaults.retain(|(k, _)| k != key);
+        self.1.defaults.push((key.clone(), default));
+    }
+
+    /// Takes the values of key until there are no more.
+    fn take_all(&mut self, key: &Key) -> Vec<OsString> {
+        std::iter::from_fn(|| self.take(key).ok()).collect()
+    }
+}
+
+impl PartialEq for Jargon {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Jargon {}
+
+impl PartialOrd for Jargon {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Jargon {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Parses the value given to key.
+pub(crate) fn parse<T: FromStr>(key: &Key, value: OsString) -> Result<T, Error>
+where
+    T::Err: Display,
+{
+    parse_occurrence(key, value, None)
+}
+
+/// Works like `parse`, but the error names which occurrence of key the value was given to.
+pub(crate) fn parse_occurrence<T: FromStr>(
+    key: &Key,
+    value: OsString,
+    occurrence: Option<usize>,
+) -> Result<T, Error>
+where
+    T::Err: Display,
+{
+    let invalid = |message: String| Error::InvalidValue {
+        key: key.clone(),
+        value: value.to_string_lossy().into_owned(),
+        message,
+        occurrence,
+    };
+
+    match value.to_str() {
+        Some(s) => s.parse().map_err(|e: T::Err| invalid(e.to_string())),
+        None => Err(invalid("invalid UTF-8".to_string())),
+    }
+}
+
+/// Returns the edit distance between a and b: how many characters must be inserted, removed,
+/// replaced or swapped with their neighbour to turn one into the other.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d: Vec<Vec<usize>> = (0..=a.len())
+        .map(|i| (0..=b.len()).map(|j| if i == 0 { j } else { i }).collect())
+        .collect();
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost: usize = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+/// Where a key is inside an argument.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Spot {
+    /// The argument is the key.
+    Whole,
+    /// The argument is a key with a value attached, starting at the offset.
+    Attached(usize),
+    /// The argument is a cluster of short keys with the key's letter at the offset.
+    Cluster(usize),
+}
+
+/// Checks if arg is key, and where the key is in it. arg is only the UTF-8 part at the start of
+/// an argument unless whole is true, in which case it can also be the key as a whole. Clusters
+/// of short keys end at the letter of any of options, keys known to take a value.
+fn spot(arg: &str, whole: bool, key: &Key, options: &[Key]) -> Option<Spot> {
+    if arg.is_empty() {
+        return None;
+    }
+
+    if whole && Key::from(arg) == *key {
+        return Some(Spot::Whole);
+    }
+
+    match key {
+        Key::Dual {
+            char: c,
+            s_txt: s,
+            l_txt: l,
+        } => {
+            let s: Key = Key::Short { char: *c, txt: *s };
+            let l: Key = Key::Long {
+                char: *c,
+                txt: l.clone(),
+            };
+            spot(arg, whole, &s, options).or_else(|| spot(arg, whole, &l, options))
+        }
+        Key::Short { char: c, txt: t } => {
+            let cluster: &str = arg.strip_prefix(*c)?;
+            if t == c || cluster.starts_with(*c) {
+                return None;
+            }
+            // The letters after one that takes a value are its value, not keys.
+            let takes_value = |letter: char| {
+                options.iter().any(|k| match k {
+                    Key::Short { char, txt }
+                    | Key::Dual {
+                        char, s_txt: txt, ..
+                    } => char == c && *txt == letter,
+                    _ => false,
+                })
+            };
+            for (at, letter) in cluster.char_indices() {
+                if letter == *t {
+                    return Some(Spot::Cluster(c.len_utf8() + at));
+                }
+                if takes_value(letter) {
+                    return None;
+                }
+            }
+            None
+        }
+        Key::Long { char: c, txt: t } => {
+            let prefix: String = format!("{}{}{}=", c, c, t);
+            if arg.starts_with(&prefix) {
+                Some(Spot::Attached(prefix.len()))
+            } else {
+                None
+            }
+        }
+        Key::Sub { .. } => None,
     }
 }
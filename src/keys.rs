@@ -162,6 +162,55 @@ impl Key {
             Key::Sub { .. } => true,
         }
     }
+
+    /// Returns each way the key can be written. Key::Dual has two, `-a` and `--all`.
+    pub(crate) fn spellings(&self) -> Vec<String> {
+        match self {
+            Key::Dual {
+                char: c,
+                s_txt: s,
+                l_txt: l,
+            } => vec![format!("{}{}", c, s), format!("{}{}{}", c, c, l)],
+            key => vec![key.to_string()],
+        }
+    }
+
+    /// Returns the long half of Key::Dual, or Key::Long itself.
+    pub(crate) fn long(&self) -> Option<Key> {
+        match self {
+            Key::Dual {
+                char: c, l_txt: l, ..
+            } => Some(Key::Long {
+                char: *c,
+                txt: l.clone(),
+            }),
+            Key::Long { .. } => Some(self.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if other is this key, or the short or long half of this Key::Dual.
+    pub(crate) fn covers(&self, other: &Key) -> bool {
+        if self == other {
+            return true;
+        }
+
+        match self {
+            Key::Dual {
+                char: c,
+                s_txt: s,
+                l_txt: l,
+            } => {
+                *other == Key::Short { char: *c, txt: *s }
+                    || *other
+                        == Key::Long {
+                            char: *c,
+                            txt: l.clone(),
+                        }
+            }
+            _ => false,
+        }
+    }
 }
 
 impl From<String> for Key {
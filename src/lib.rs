@@ -16,13 +16,37 @@ to handle any type of argument, including ones that don't start with `-` & subco
 See [examples](examples/)
 */
 
+mod choices;
+mod completion;
+mod config;
+mod context;
+mod dispatch;
 mod error;
+mod from_jargon;
 mod jargons;
 mod keys;
-
+mod man;
+mod os;
+mod response;
+mod source;
+mod spec;
+
+pub use choices::*;
+pub use completion::*;
+pub use context::*;
+pub use dispatch::*;
 pub use error::*;
+pub use from_jargon::*;
 pub use jargons::*;
 pub use keys::*;
+pub use source::*;
+pub use spec::*;
+
+#[cfg(feature = "derive")]
+pub use jargon_args_derive::Jargon;
+
+#[cfg(all(test, feature = "derive"))]
+extern crate self as jargon_args;
 
 #[cfg(test)]
 mod tests;
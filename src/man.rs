@@ -0,0 +1,138 @@
+use super::Arg;
+use super::Key;
+use super::Spec;
+
+impl Spec {
+    /// Renders a section 1 man page in roff, with NAME, SYNOPSIS, DESCRIPTION, OPTIONS and
+    /// SUBCOMMANDS sections. Sections with nothing declared are left out, and nested
+    /// subcommands are listed by their full path with their own options.
+    ///
+    /// ```
+    /// use jargon_args::{Arg, Spec};
+    ///
+    /// let spec: Spec = Spec::new("basename")
+    ///     .about("Print NAME with any leading directory components removed.")
+    ///     .arg(Arg::option(["-s", "--suffix"], "SUFFIX").help("remove a trailing SUFFIX"));
+    ///
+    /// let man: String = spec.man();
+    /// assert!(man.starts_with(".TH BASENAME 1\n.SH NAME\nbasename \\- Print NAME"));
+    /// assert!(man.contains(".TP\n\\fB\\-s\\fR, \\fB\\-\\-suffix\\fR=\\fISUFFIX\\fR\n"));
+    /// ```
+    pub fn man(&self) -> String {
+        let mut man: String = format!(".TH {} 1\n", roff(&self.name.to_uppercase()));
+
+        man.push_str(".SH NAME\n");
+        man.push_str(&roff(&self.name));
+        if !self.summary().is_empty() {
+            man.push_str(&format!(" \\- {}", roff(self.summary())));
+        }
+        man.push('\n');
+
+        man.push_str(".SH SYNOPSIS\n");
+        man.push_str(&synopsis(&self.name, &self.usage));
+
+        if let Some(about) = &self.about {
+            man.push_str(".SH DESCRIPTION\n");
+            man.push_str(&paragraphs(about));
+        }
+
+        if !self.args.is_empty() {
+            man.push_str(".SH OPTIONS\n");
+            for arg in &self.args {
+                man.push_str(&item(arg));
+            }
+        }
+
+        if !self.subcommands.is_empty() {
+            man.push_str(".SH SUBCOMMANDS\n");
+            for subcommand in &self.subcommands {
+                man.push_str(&subcommands(subcommand, &subcommand.name));
+            }
+        }
+
+        man
+    }
+}
+
+/// Escapes s for roff, so that dashes, backslashes and leading dots print as written.
+fn roff(s: &str) -> String {
+    let escaped: String = s.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+/// Renders text, each line escaped, with blank lines starting new paragraphs.
+fn paragraphs(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                ".PP\n".to_string()
+            } else {
+                format!("{}\n", roff(line))
+            }
+        })
+        .collect()
+}
+
+/// Renders the usage lines of a command, one per line, or `[OPTION]...` without any.
+fn synopsis(name: &str, usage: &[String]) -> String {
+    if usage.is_empty() {
+        return format!("\\fB{}\\fR [OPTION]...\n", roff(name));
+    }
+
+    usage
+        .iter()
+        .map(|usage| format!("\\fB{}\\fR {}\n", roff(name), roff(usage)))
+        .collect::<Vec<String>>()
+        .join(".br\n")
+}
+
+/// Renders an argument as a tagged paragraph, like `-s, --suffix=SUFFIX` over its description.
+fn item(arg: &Arg) -> String {
+    let key: String = arg
+        .key
+        .spellings()
+        .iter()
+        .map(|k| format!("\\fB{}\\fR", roff(k)))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let tag: String = match (&arg.key, &arg.value) {
+        (_, None) => key,
+        (Key::Short { .. }, Some(value)) | (Key::Sub { .. }, Some(value)) => {
+            format!("{} \\fI{}\\fR", key, roff(value))
+        }
+        (_, Some(value)) => format!("{}=\\fI{}\\fR", key, roff(value)),
+    };
+
+    format!(".TP\n{}\n{}", tag, paragraphs(&arg.right()))
+}
+
+/// Renders a subcommand, its options indented below it, then its own subcommands.
+fn subcommands(spec: &Spec, path: &str) -> String {
+    let mut man: String = format!(".TP\n\\fB{}\\fR", roff(path));
+    if let Some(usage) = spec.usage.first() {
+        man.push_str(&format!(" {}", roff(usage)));
+    }
+    man.push('\n');
+    man.push_str(&paragraphs(spec.summary()));
+
+    if !spec.args.is_empty() {
+        man.push_str(".RS\n");
+        for arg in &spec.args {
+            man.push_str(&item(arg));
+        }
+        man.push_str(".RE\n");
+    }
+
+    for subcommand in &spec.subcommands {
+        man.push_str(&subcommands(
+            subcommand,
+            &format!("{} {}", path, subcommand.name),
+        ));
+    }
+    man
+}
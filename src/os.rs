@@ -0,0 +1,42 @@
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+
+/// Returns the longest UTF-8 prefix of an argument. Keys are only ever looked for in it.
+pub(crate) fn utf8(arg: &OsStr) -> Cow<'_, str> {
+    match arg.to_str() {
+        Some(s) => Cow::Borrowed(s),
+        None => {
+            let lossy: String = arg.to_string_lossy().into_owned();
+            let end: usize = lossy.find('\u{FFFD}').unwrap_or(lossy.len());
+            Cow::Owned(lossy[..end].to_string())
+        }
+    }
+}
+
+/// Splits an argument in two at the byte index `at` of its UTF-8 prefix. Works like
+/// `String::split_off`, so the argument keeps `[0, at)` and `[at, len)` is returned.
+pub(crate) fn split_off(arg: &mut OsString, at: usize) -> OsString {
+    let head: String = utf8(arg)[..at].to_string();
+    let tail: OsString = tail(arg, at);
+    *arg = head.into();
+    tail
+}
+
+#[cfg(unix)]
+fn tail(arg: &OsStr, at: usize) -> OsString {
+    use std::os::unix::ffi::OsStrExt;
+    OsStr::from_bytes(&arg.as_bytes()[at..]).to_os_string()
+}
+
+#[cfg(windows)]
+fn tail(arg: &OsStr, at: usize) -> OsString {
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+    let skip: usize = utf8(arg)[..at].encode_utf16().count();
+    let wide: Vec<u16> = arg.encode_wide().skip(skip).collect();
+    OsString::from_wide(&wide)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn tail(arg: &OsStr, at: usize) -> OsString {
+    arg.to_string_lossy()[at..].into()
+}
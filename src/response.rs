@@ -0,0 +1,213 @@
+use super::os;
+use super::Error;
+use super::Jargon;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+impl Jargon {
+    /// Expands every `@file` argument into the arguments written in file, like compilers do to
+    /// get past command line length limits. Arguments after `--` are left as they are.
+    ///
+    /// Arguments in a file are split on whitespace, and read like a shell would: `'single'` and
+    /// `"double"` quotes keep whitespace, a backslash escapes the character after it, and `#`
+    /// starts a comment until the end of the line. A file can name other files with `@file`,
+    /// relative to its own directory, unless the `@` is quoted.
+    ///
+    /// A file that can't be read, an unterminated quote, or a file that includes itself return
+    /// Err(jargon_args::Error::ResponseFile) naming the file and line, and leave arguments as
+    /// they were.
+    ///
+    /// ```
+    /// use jargon_args::Jargon;
+    ///
+    /// let file = std::env::temp_dir().join("jargon_response_files_doc");
+    /// std::fs::write(&file, "-o 'hello world.o' # the object\n-Wall\n").unwrap();
+    ///
+    /// let mut j: Jargon = Jargon::from_vec(vec![
+    ///     "cc".to_string(),
+    ///     format!("@{}", file.display()),
+    ///     "hello.c".to_string(),
+    /// ]);
+    /// j.response_files().unwrap();
+    ///
+    /// assert_eq!(j.option_arg("-o"), Some("hello world.o".to_string()));
+    /// assert_eq!(j.finish(), vec!["-Wall".to_string(), "hello.c".to_string()]);
+    /// ```
+    pub fn response_files(&mut self) -> Result<(), Error> {
+        let mut args: Vec<OsString> = Vec::new();
+        let mut literal: bool = false;
+
+        for (i, arg) in self.0.iter().enumerate() {
+            match include(arg) {
+                Some(file) if i > 0 && !literal => {
+                    expand(&file, None, &mut Vec::new(), &mut args, &mut literal)?
+                }
+                _ => {
+                    literal |= i > 0 && arg == "--";
+                    args.push(arg.clone());
+                }
+            }
+        }
+
+        self.0 = args;
+        Ok(())
+    }
+}
+
+/// An argument read from a response file.
+struct Word {
+    text: String,
+    /// The line it starts on.
+    line: usize,
+    /// Whether its first character was quoted or escaped, so a leading `@` is kept as is.
+    quoted: bool,
+}
+
+impl Word {
+    fn new(line: usize, quoted: bool) -> Self {
+        Self {
+            text: String::new(),
+            line,
+            quoted,
+        }
+    }
+}
+
+/// Returns the file an `@file` argument names.
+fn include(arg: &OsString) -> Option<PathBuf> {
+    let text = os::utf8(arg);
+    if !text.starts_with('@') || arg.len() == 1 {
+        return None;
+    }
+
+    let mut arg: OsString = arg.clone();
+    Some(os::split_off(&mut arg, 1).into())
+}
+
+/// Pushes the arguments in file to args, expanding the files it includes. from is the file and
+/// line that included it, and stack the files being expanded, to catch files that include
+/// themselves.
+fn expand(
+    file: &Path,
+    from: Option<(&Path, usize)>,
+    stack: &mut Vec<PathBuf>,
+    args: &mut Vec<OsString>,
+    literal: &mut bool,
+) -> Result<(), Error> {
+    let fail = |message: String| match from {
+        Some((including, line)) => error(including, Some(line), message),
+        None => error(file, None, message),
+    };
+    let unreadable = |e: std::io::Error| match from {
+        Some(_) => fail(format!("cannot read '{}': {}", file.display(), e)),
+        None => fail(e.to_string()),
+    };
+
+    let canonical: PathBuf = fs::canonicalize(file).map_err(&unreadable)?;
+    if stack.contains(&canonical) {
+        return Err(fail(format!("'{}' includes itself", file.display())));
+    }
+    let text: String = fs::read_to_string(&canonical).map_err(&unreadable)?;
+
+    stack.push(canonical);
+    let dir: &Path = file.parent().unwrap_or_else(|| Path::new(""));
+    for word in words(&text).map_err(|(line, message)| error(file, Some(line), message))? {
+        if !*literal && !word.quoted && word.text.len() > 1 && word.text.starts_with('@') {
+            expand(
+                &dir.join(&word.text[1..]),
+                Some((file, word.line)),
+                stack,
+                args,
+                literal,
+            )?;
+        } else {
+            *literal |= word.text == "--";
+            args.push(word.text.into());
+        }
+    }
+    stack.pop();
+
+    Ok(())
+}
+
+fn error(file: &Path, line: Option<usize>, message: String) -> Error {
+    Error::ResponseFile {
+        file: file.display().to_string(),
+        line,
+        message,
+    }
+}
+
+/// Splits the text of a response file into arguments, or returns the line and reason it can't.
+fn words(text: &str) -> Result<Vec<Word>, (usize, String)> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut word: Option<Word> = None;
+    let mut line: usize = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' if word.is_none() => while chars.next_if(|c| *c != '\n').is_some() {},
+            '\'' => {
+                let start: usize = line;
+                let w: &mut Word = word.get_or_insert_with(|| Word::new(line, true));
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => {
+                            line += (c == '\n') as usize;
+                            w.text.push(c);
+                        }
+                        None => return Err((start, "unterminated single quote".to_string())),
+                    }
+                }
+            }
+            '"' => {
+                let start: usize = line;
+                let w: &mut Word = word.get_or_insert_with(|| Word::new(line, true));
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('\n') => line += 1,
+                            Some(c) if matches!(c, '"' | '\\' | '$' | '`') => w.text.push(c),
+                            Some(c) => {
+                                w.text.push('\\');
+                                w.text.push(c);
+                            }
+                            None => return Err((start, "unterminated double quote".to_string())),
+                        },
+                        Some(c) => {
+                            line += (c == '\n') as usize;
+                            w.text.push(c);
+                        }
+                        None => return Err((start, "unterminated double quote".to_string())),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') => line += 1,
+                Some(c) => word
+                    .get_or_insert_with(|| Word::new(line, true))
+                    .text
+                    .push(c),
+                None => word
+                    .get_or_insert_with(|| Word::new(line, false))
+                    .text
+                    .push('\\'),
+            },
+            c if c.is_whitespace() => {
+                words.extend(word.take());
+                line += (c == '\n') as usize;
+            }
+            c => word
+                .get_or_insert_with(|| Word::new(line, false))
+                .text
+                .push(c),
+        }
+    }
+    words.extend(word.take());
+
+    Ok(words)
+}
@@ -0,0 +1,39 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// # Source
+///
+/// This Enum tells where the value of a key came from.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub enum Source {
+    /// # CommandLine
+    ///
+    /// The value was given in arguments.
+    CommandLine,
+
+    /// # Environment(String)
+    ///
+    /// The value came from the environment variable the key is bound to.
+    Environment(String),
+
+    /// # Config(PathBuf)
+    ///
+    /// The value came from the configuration file at this path.
+    Config(PathBuf),
+
+    /// # Default
+    ///
+    /// The value is the default, as the key was not given.
+    Default,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::CommandLine => write!(f, "command line"),
+            Source::Environment(var) => write!(f, "environment variable '{}'", var),
+            Source::Config(file) => write!(f, "config file '{}'", file.display()),
+            Source::Default => write!(f, "default"),
+        }
+    }
+}
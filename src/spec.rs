@@ -0,0 +1,444 @@
+use super::choices::{choose, pick};
+use super::jargons::{parse, parse_occurrence};
+use super::Choices;
+use super::Error;
+use super::Jargon;
+use super::Key;
+use super::Source;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// # Spec
+///
+/// This struct declares a program's arguments in one place. The same declarations are used to
+/// parse arguments out of a Jargon and to render GNU style help text, so the two never drift.
+///
+/// # Example
+///
+/// ```
+/// use jargon_args::{Arg, Jargon, Spec};
+///
+/// let spec: Spec = Spec::new("basename")
+///     .usage("NAME [SUFFIX]")
+///     .about("Print NAME with any leading directory components removed.")
+///     .arg(Arg::flag(["-a", "--multiple"]).help("support multiple arguments"))
+///     .arg(Arg::option(["-s", "--suffix"], "SUFFIX").help("remove a trailing SUFFIX"));
+///
+/// let mut j: Jargon = Jargon::from_vec(vec!["basename", "-a", "--suffix=.txt", "a.txt"]);
+/// let m = spec.parse(&mut j).unwrap();
+///
+/// assert!(m.contains("--multiple"));
+/// assert_eq!(m.option_arg("-s"), Some(".txt".to_string()));
+/// assert_eq!(j.finish(), vec!["a.txt".to_string()]);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Spec {
+    pub(crate) name: String,
+    pub(crate) usage: Vec<String>,
+    pub(crate) about: Option<String>,
+    pub(crate) args: Vec<Arg>,
+    pub(crate) subcommands: Vec<Spec>,
+}
+
+impl Spec {
+    /// Starts a spec for the program or subcommand called name.
+    pub fn new<T: ToString>(name: T) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a usage line, which follows the program's name. The first line starts with `Usage:`,
+    /// and the ones after with `or:`. Without any, the usage is `[OPTION]...`.
+    pub fn usage<T: ToString>(mut self, usage: T) -> Self {
+        self.usage.push(usage.to_string());
+        self
+    }
+
+    /// Sets the description printed below the usage lines.
+    pub fn about<T: ToString>(mut self, about: T) -> Self {
+        self.about = Some(about.to_string());
+        self
+    }
+
+    /// Declares an argument.
+    pub fn arg(mut self, arg: Arg) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// Declares a subcommand, described by its own spec.
+    pub fn subcommand(mut self, subcommand: Spec) -> Self {
+        self.subcommands.push(subcommand);
+        self
+    }
+
+    /// Takes the defaults a Jargon was given through `option_arg_or` and `option_arg_or_else`
+    /// for declared arguments which have none, so help shows them.
+    pub fn defaults(mut self, jargon: &Jargon) -> Self {
+        for (key, default) in jargon.defaults() {
+            if let Some(arg) = self
+                .args
+                .iter_mut()
+                .find(|arg| arg.default.is_none() && arg.key.covers(&key))
+            {
+                arg.default = Some(default);
+            }
+        }
+        self
+    }
+
+    /// Removes every declared argument from provided Jargon, and returns what was found in
+    /// Ok(Matches). Arguments which were not declared are left in the Jargon.
+    /// Err(jargon_args::Error) if an option is given without a value, or with one that isn't
+    /// one of its choices.
+    pub fn parse(&self, jargon: &mut Jargon) -> Result<Matches, Error> {
+        jargon.options(
+            self.args
+                .iter()
+                .filter(|arg| arg.value.is_some())
+                .map(|arg| arg.key.clone()),
+        );
+        for arg in &self.args {
+            jargon.query(&arg.key);
+            if let Some(var) = &arg.env {
+                jargon.bind_env(arg.key.clone(), var);
+            }
+        }
+        for subcommand in &self.subcommands {
+            jargon.query(&Key::from(subcommand.name.as_str()));
+        }
+
+        let mut found: Vec<(Arg, usize, Vec<OsString>, Option<Source>)> = self
+            .args
+            .iter()
+            .map(|arg| (arg.clone(), 0, Vec::new(), None))
+            .collect();
+
+        // Options are taken in the order they were given, so that the value of one can never be
+        // mistaken for a cluster of short keys.
+        while let Some((_, n)) = found
+            .iter()
+            .enumerate()
+            .filter(|(_, (arg, _, _, _))| arg.value.is_some())
+            .filter_map(|(n, (arg, _, _, _))| jargon.position(&arg.key).map(|p| (p, n)))
+            .min()
+        {
+            let value: OsString = jargon.take(&found[n].0.key)?;
+            jargon.record(&found[n].0.key, Source::CommandLine);
+            found[n].1 += 1;
+            found[n].2.push(value);
+            found[n].3 = Some(Source::CommandLine);
+        }
+
+        for (arg, count, values, source) in found.iter_mut() {
+            if arg.value.is_none() {
+                *count = jargon.count(arg.key.clone());
+                if *count > 0 {
+                    *source = jargon.source(arg.key.clone());
+                }
+            } else if values.is_empty() {
+                if let Ok((value, from)) = jargon.fallback(&arg.key) {
+                    jargon.record(&arg.key, from.clone());
+                    values.push(value);
+                    *source = Some(from);
+                }
+            }
+
+            if !arg.choices.is_empty() {
+                for value in values.iter_mut() {
+                    let i: usize = pick(&arg.key, value.clone(), &arg.choices, arg.ignore_case)?;
+                    *value = arg.choices[i].clone().into();
+                }
+            }
+        }
+
+        Ok(Matches(found))
+    }
+
+    /// Renders the help text.
+    pub fn help(&self) -> String {
+        let mut help: String = String::new();
+
+        if self.usage.is_empty() {
+            help.push_str(&format!("Usage: {} [OPTION]...\n", self.name));
+        }
+        for (i, usage) in self.usage.iter().enumerate() {
+            let prefix: &str = if i == 0 { "Usage:" } else { "  or: " };
+            help.push_str(&format!("{} {} {}\n", prefix, self.name, usage));
+        }
+
+        if let Some(about) = &self.about {
+            help.push_str(&format!("{}\n", about));
+        }
+
+        if !self.args.is_empty() {
+            help.push('\n');
+        }
+        let lefts: Vec<String> = self.args.iter().map(Arg::left).collect();
+        let width: usize = lefts.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        for (arg, left) in self.args.iter().zip(lefts) {
+            let line: String = format!("  {:width$}  {}", left, arg.right(), width = width);
+            help.push_str(line.trim_end());
+            help.push('\n');
+        }
+
+        if !self.subcommands.is_empty() {
+            help.push_str("\nSubcommands:\n");
+        }
+        let width: usize = self
+            .subcommands
+            .iter()
+            .map(|s| s.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        for subcommand in &self.subcommands {
+            let about: &str = subcommand.summary();
+            let line: String = format!("  {:width$}  {}", subcommand.name, about, width = width);
+            help.push_str(line.trim_end());
+            help.push('\n');
+        }
+
+        help
+    }
+
+    /// Returns the first line of the description, used where subcommands are listed.
+    pub(crate) fn summary(&self) -> &str {
+        self.about
+            .as_deref()
+            .and_then(|about| about.lines().next())
+            .unwrap_or("")
+    }
+}
+
+/// # Arg
+///
+/// This struct declares one argument of a Spec: its key, the name of its value if it takes one,
+/// its description, its default value, the environment variable it falls back to and the values
+/// it accepts.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Arg {
+    pub(crate) key: Key,
+    pub(crate) value: Option<String>,
+    pub(crate) help: String,
+    pub(crate) default: Option<String>,
+    pub(crate) env: Option<String>,
+    pub(crate) choices: Vec<String>,
+    pub(crate) ignore_case: bool,
+}
+
+impl Arg {
+    /// Declares a flag, an argument that takes no value, like `-a, --all`.
+    pub fn flag<K: Into<Key>>(key: K) -> Self {
+        Self {
+            key: key.into(),
+            value: None,
+            help: String::new(),
+            default: None,
+            env: None,
+            choices: Vec::new(),
+            ignore_case: false,
+        }
+    }
+
+    /// Declares an option, an argument that takes a value, like `-s, --suffix=SUFFIX`. value is
+    /// the name of the value in help text.
+    pub fn option<K: Into<Key>, T: ToString>(key: K, value: T) -> Self {
+        Self {
+            value: Some(value.to_string()),
+            ..Self::flag(key)
+        }
+    }
+
+    /// Sets the description of the argument.
+    pub fn help<T: ToString>(mut self, help: T) -> Self {
+        self.help = help.to_string();
+        self
+    }
+
+    /// Sets the value used when an option is not given.
+    pub fn default<T: ToString>(mut self, default: T) -> Self {
+        self.default = Some(default.to_string());
+        self
+    }
+
+    /// Sets the environment variable used when an option is not given, before its default.
+    pub fn env<T: ToString>(mut self, var: T) -> Self {
+        self.env = Some(var.to_string());
+        self
+    }
+
+    /// Restricts the values of an option to choices, listed in help and completed by shells.
+    /// `Spec::parse` returns Err(jargon_args::Error::InvalidChoice) for any other value. Use
+    /// `Choices::spellings` to accept the spellings of a type.
+    pub fn choices<I, T>(mut self, choices: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.choices = choices.into_iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Makes choices match whatever their case. Values are then spelled as the choice they
+    /// match.
+    pub fn ignore_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    /// Renders the key and value name, like `-s, --suffix=SUFFIX`.
+    fn left(&self) -> String {
+        let key: String = match &self.key {
+            Key::Long { .. } => format!("    {}", self.key),
+            key => key.to_string(),
+        };
+
+        match (&self.key, &self.value) {
+            (_, None) => key,
+            (Key::Short { .. }, Some(value)) | (Key::Sub { .. }, Some(value)) => {
+                format!("{} {}", key, value)
+            }
+            (_, Some(value)) => format!("{}={}", key, value),
+        }
+    }
+
+    /// Renders the description, with the default value, environment variable and accepted
+    /// values if there are.
+    pub(crate) fn right(&self) -> String {
+        let mut right: String = self.help.clone();
+        if let Some(default) = &self.default {
+            right.push_str(&format!(" (default: {})", default));
+        }
+        if let Some(var) = &self.env {
+            right.push_str(&format!(" [env: {}]", var));
+        }
+        if !self.choices.is_empty() {
+            right.push_str(&format!(" [possible values: {}]", self.choices.join(", ")));
+        }
+        right.trim_start().to_string()
+    }
+}
+
+/// # Matches
+///
+/// This struct holds the arguments a Spec found in a Jargon. Its getters take any key of a
+/// declared argument, so `"-s"`, `"--suffix"` or `["-s", "--suffix"]` all find `-s, --suffix`.
+/// Environment variables, then configuration files, then declared defaults, are used for
+/// options which were not given.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Matches(pub(crate) Vec<(Arg, usize, Vec<OsString>, Option<Source>)>);
+
+impl Matches {
+    /// Checks if provided key was given.
+    pub fn contains<K: Into<Key>>(&self, key: K) -> bool {
+        self.count(key) > 0
+    }
+
+    /// Counts how many times provided key was given.
+    pub fn count<K: Into<Key>>(&self, key: K) -> usize {
+        self.get(&key.into()).map_or(0, |(_, count, _, _)| *count)
+    }
+
+    /// Returns Some(T) with the first value of provided key, or its default. None if there is
+    /// neither or the value fails to parse.
+    pub fn option_arg<T: FromStr, K: Into<Key>>(&self, key: K) -> Option<T> {
+        self.values(&key.into())
+            .into_iter()
+            .next()?
+            .into_string()
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    /// Returns Ok(T) with the first value of provided key, or its default.
+    /// Err(jargon_args::Error) if there is neither or the value fails to parse.
+    pub fn result_arg<T: FromStr, K: Into<Key>>(&self, key: K) -> Result<T, Error>
+    where
+        T::Err: Display,
+    {
+        let key: Key = key.into();
+        match (self.values(&key).into_iter().next(), self.get(&key)) {
+            (Some(value), _) => parse(&key, value),
+            (None, Some((Arg { env: Some(var), .. }, _, _, _))) => Err(Error::MissingArgEnv {
+                key,
+                var: var.clone(),
+            }),
+            (None, _) => Err(Error::MissingArg(key)),
+        }
+    }
+
+    /// Works like `option_arg`, but for a type that declares its spellings with
+    /// `jargon_args::Choices`. None if there is no value or it is none of them.
+    pub fn option_choice<T: Choices, K: Into<Key>>(&self, key: K) -> Option<T> {
+        self.result_choice(key).ok()
+    }
+
+    /// Works like `result_arg`, but for a type that declares its spellings with
+    /// `jargon_args::Choices`. Err(jargon_args::Error::InvalidChoice) listing them if the value
+    /// is none of them.
+    pub fn result_choice<T: Choices, K: Into<Key>>(&self, key: K) -> Result<T, Error> {
+        let key: Key = key.into();
+        let value: OsString = self.result_arg(key.clone())?;
+        choose(&key, value)
+    }
+
+    /// Returns every value of provided key, or its default. Values that fail to parse are
+    /// skipped.
+    pub fn option_args<T: FromStr, K: Into<Key>>(&self, key: K) -> Vec<T> {
+        self.values(&key.into())
+            .into_iter()
+            .filter_map(|v| v.into_string().ok()?.parse().ok())
+            .collect()
+    }
+
+    /// Returns `Ok(Vec<T>)` with every value of provided key, or its default.
+    /// Err(jargon_args::Error) if there is none, or for the first value that fails to parse.
+    pub fn result_args<T: FromStr, K: Into<Key>>(&self, key: K) -> Result<Vec<T>, Error>
+    where
+        T::Err: Display,
+    {
+        let key: Key = key.into();
+        let values: Vec<OsString> = self.values(&key);
+        if values.is_empty() {
+            return self.result_arg(key).map(|value| vec![value]);
+        }
+        let given: bool = self.source(key.clone()) == Some(Source::CommandLine);
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| parse_occurrence(&key, value, if given { Some(i + 1) } else { None }))
+            .collect()
+    }
+
+    /// Works like `option_arg`, but returns the value as an `OsString`, `PathBuf` or anything
+    /// else made from an `OsString`.
+    pub fn option_arg_os<T: From<OsString>, K: Into<Key>>(&self, key: K) -> Option<T> {
+        self.values(&key.into()).into_iter().next().map(T::from)
+    }
+
+    /// Returns where the value of provided key came from. None if it has no value.
+    pub fn source<K: Into<Key>>(&self, key: K) -> Option<Source> {
+        let (arg, _, _, source) = self.get(&key.into())?;
+        source
+            .clone()
+            .or_else(|| arg.default.as_ref().map(|_| Source::Default))
+    }
+
+    /// Finds the declared argument provided key belongs to.
+    fn get(&self, key: &Key) -> Option<&(Arg, usize, Vec<OsString>, Option<Source>)> {
+        self.0.iter().find(|(arg, _, _, _)| arg.key.covers(key))
+    }
+
+    /// Returns the values given to key, or its default.
+    fn values(&self, key: &Key) -> Vec<OsString> {
+        match self.get(key) {
+            Some((_, _, values, _)) if !values.is_empty() => values.clone(),
+            Some((arg, _, _, _)) => arg.default.iter().map(OsString::from).collect(),
+            None => Vec::new(),
+        }
+    }
+}
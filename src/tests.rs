@@ -1,4 +1,6 @@
-use crate::{Error, Jargon, Key};
+use crate::{Arg, Choices, Context, Error, Jargon, Key, Shell, Source, Spec, Unknown};
+use std::ffi::OsString;
+use std::path::PathBuf;
 
 #[test]
 fn short_key() {
@@ -106,16 +108,13 @@ fn jargon() {
     ]);
 
     assert_eq!(
-        j,
-        Jargon {
-            0: [
-                "jargon".to_string(),
-                "-a".to_string(),
-                "-b".to_string(),
-                "beans".to_string(),
-            ]
-            .to_vec(),
-        }
+        j.0,
+        vec![
+            OsString::from("jargon"),
+            OsString::from("-a"),
+            OsString::from("-b"),
+            OsString::from("beans"),
+        ]
     )
 }
 
@@ -147,7 +146,7 @@ fn jargon_contains_short_t() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-a".to_string(),
             "-b".to_string(),
@@ -169,7 +168,7 @@ fn jargon_contains_short_f() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -190,7 +189,7 @@ fn jargon_contains_short_t_rmv() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -210,7 +209,7 @@ fn jargon_contains_short_f_rmv() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -232,7 +231,7 @@ fn jargon_contains_long_t() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "--all".to_string(),
             "-b".to_string(),
@@ -254,7 +253,7 @@ fn jargon_contains_long_f() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -275,7 +274,7 @@ fn jargon_contains_long_t_rmv() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -295,7 +294,7 @@ fn jargon_contains_long_f_rmv() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -317,7 +316,7 @@ fn jargon_contains_dual_s_t() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-a".to_string(),
             "-b".to_string(),
@@ -339,7 +338,7 @@ fn jargon_contains_dual_s_f() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -360,7 +359,7 @@ fn jargon_contains_dual_s_t_rmv() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -380,7 +379,7 @@ fn jargon_contains_dual_l_f_rmv() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -402,7 +401,7 @@ fn jargon_contains_dual_l_t() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "--all".to_string(),
             "-b".to_string(),
@@ -424,7 +423,7 @@ fn jargon_contains_dual_l_f() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -445,7 +444,7 @@ fn jargon_contains_dual_l_t_rmv() {
 
     assert_eq!(
         j,
-        Jargon(vec![
+        Jargon::from_vec(vec![
             "jargon".to_string(),
             "-b".to_string(),
             "beans".to_string(),
@@ -597,7 +596,10 @@ fn jargon_arg_option_dual_l_t() {
         "world".to_string(),
     ]);
 
-    assert_eq!(j.option_arg::<String, [&str; 2]>(["-a", "--all"]), Some("hello".to_string()),)
+    assert_eq!(
+        j.option_arg::<String, [&str; 2]>(["-a", "--all"]),
+        Some("hello".to_string()),
+    )
 }
 
 #[test]
@@ -634,302 +636,1115 @@ fn jargon_arg_option_dual_l_f() {
 }
 
 #[test]
-fn key_short_is_short() {
-    let k: Key = "-a".into();
-    assert!(k.is_short())
-}
+fn jargon_arg_option_long_attached() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_long_attached".to_string(),
+        "--suffix=.txt".to_string(),
+        "world".to_string(),
+    ]);
 
-#[test]
-fn key_short_is_long() {
-    let k: Key = "-a".into();
-    assert!(!k.is_long())
+    assert_eq!(j.option_arg("--suffix"), Some(".txt".to_string()),);
+    assert_eq!(j.finish(), vec!["world".to_string()],)
 }
 
 #[test]
-fn key_short_is_dual() {
-    let k: Key = "-a".into();
-    assert!(!k.is_dual())
-}
+fn jargon_arg_option_long_attached_empty() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_long_attached_empty".to_string(),
+        "--suffix=".to_string(),
+    ]);
 
-#[test]
-fn key_short_is_sub() {
-    let k: Key = "-a".into();
-    assert!(!k.is_sub())
+    assert_eq!(j.option_arg("--suffix"), Some("".to_string()),)
 }
 
 #[test]
-fn key_long_is_short() {
-    let k: Key = "--all".into();
-    assert!(!k.is_short())
-}
+fn jargon_arg_option_long_attached_dash() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_long_attached_dash".to_string(),
+        "--offset=-5".to_string(),
+    ]);
 
-#[test]
-fn key_long_is_long() {
-    let k: Key = "--all".into();
-    assert!(k.is_long())
+    assert_eq!(j.option_arg("--offset"), Some(-5),)
 }
 
 #[test]
-fn key_long_is_dual() {
-    let k: Key = "--all".into();
-    assert!(!k.is_dual())
-}
+fn jargon_arg_option_long_attached_other() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_long_attached_other".to_string(),
+        "--suffixes=.txt".to_string(),
+    ]);
 
-#[test]
-fn key_long_is_sub() {
-    let k: Key = "--all".into();
-    assert!(!k.is_sub())
+    assert_eq!(j.option_arg::<String, &str>("--suffix"), None,);
+    assert_eq!(j.finish(), vec!["--suffixes=.txt".to_string()],)
 }
 
 #[test]
-fn key_dual_is_short() {
-    let k: Key = ["-a", "--all"].into();
-    assert!(!k.is_short())
+fn jargon_arg_option_short_attached() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_short_attached".to_string(),
+        "-ofile".to_string(),
+        "world".to_string(),
+    ]);
+
+    assert_eq!(j.option_arg("-o"), Some("file".to_string()),);
+    assert_eq!(j.finish(), vec!["world".to_string()],)
 }
 
 #[test]
-fn key_dual_is_long() {
-    let k: Key = ["-a", "--all"].into();
-    assert!(!k.is_long())
+fn jargon_arg_option_dual_attached() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_dual_attached".to_string(),
+        "-ofile".to_string(),
+        "--output=other".to_string(),
+    ]);
+
+    assert_eq!(j.option_arg(["-o", "--output"]), Some("file".to_string()),);
+    assert_eq!(j.option_arg(["-o", "--output"]), Some("other".to_string()),);
+    assert!(j.finish().is_empty())
 }
 
 #[test]
-fn key_dual_is_dual() {
-    let k: Key = ["-a", "--all"].into();
-    assert!(k.is_dual())
+fn jargon_result_arg_attached() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_result_arg_attached".to_string(),
+        "--port=8080".to_string(),
+    ]);
+
+    assert_eq!(j.result_arg("--port"), Ok(8080u16),)
 }
 
 #[test]
-fn key_dual_is_sub() {
-    let k: Key = ["-a", "--all"].into();
-    assert!(!k.is_sub())
+fn jargon_contains_long_attached() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_contains_long_attached".to_string(),
+        "--color=always".to_string(),
+        "world".to_string(),
+    ]);
+
+    assert!(j.contains("--color"));
+    assert_eq!(j.finish(), vec!["world".to_string()],)
 }
 
 #[test]
-fn key_sub_is_short() {
-    let k: Key = "subcmd".into();
-    assert!(!k.is_short())
+fn jargon_contains_cluster() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_contains_cluster".to_string(),
+        "-xvf".to_string(),
+        "archive.tar".to_string(),
+    ]);
+
+    assert!(j.contains(["-v", "--verbose"]));
+    assert!(!j.contains("-v"));
+    assert!(j.contains("-x"));
+    assert_eq!(
+        j.finish(),
+        vec!["-f".to_string(), "archive.tar".to_string()],
+    )
 }
 
 #[test]
-fn key_sub_is_long() {
-    let k: Key = "subcmd".into();
-    assert!(!k.is_long())
+fn jargon_contains_cluster_last() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_contains_cluster_last".to_string(),
+        "-xv".to_string(),
+    ]);
+
+    assert!(j.contains("-x"));
+    assert!(j.contains("-v"));
+    assert!(j.finish().is_empty())
 }
 
 #[test]
-fn key_sub_is_dual() {
-    let k: Key = "subcmd".into();
-    assert!(!k.is_dual())
+fn jargon_contains_cluster_not_long() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_contains_cluster_not_long".to_string(),
+        "--verbose".to_string(),
+    ]);
+
+    assert!(!j.contains("-v"));
+    assert_eq!(j.finish(), vec!["--verbose".to_string()],)
 }
 
 #[test]
-fn key_sub_is_sub() {
-    let k: Key = "subcmd".into();
-    assert!(k.is_sub())
+fn jargon_arg_option_cluster_next() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_cluster_next".to_string(),
+        "-xvf".to_string(),
+        "archive.tar".to_string(),
+        "world".to_string(),
+    ]);
+
+    assert_eq!(j.option_arg("-f"), Some("archive.tar".to_string()),);
+    assert!(j.contains("-x"));
+    assert!(j.contains("-v"));
+    assert_eq!(j.finish(), vec!["world".to_string()],)
 }
 
 #[test]
-fn key_short_char() {
-    let k: Key = "-a".into();
-    assert_eq!(k.char(), '-',)
+fn jargon_arg_option_cluster_rest() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_cluster_rest".to_string(),
+        "-xfarchive.tar".to_string(),
+        "world".to_string(),
+    ]);
+
+    assert_eq!(
+        j.option_arg(["-f", "--file"]),
+        Some("archive.tar".to_string()),
+    );
+    assert_eq!(j.finish(), vec!["-x".to_string(), "world".to_string()],)
 }
 
 #[test]
-fn key_long_char() {
-    let k: Key = "--all".into();
-    assert_eq!(k.char(), '-')
+fn jargon_arg_option_attached_before_flags() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_attached_before_flags".to_string(),
+        "-ofile".to_string(),
+        "-lf".to_string(),
+    ]);
+
+    assert_eq!(j.option_arg("-o"), Some("file".to_string()),);
+    assert!(j.contains("-f"));
+    assert!(j.contains("-l"));
+    assert!(j.finish().is_empty())
 }
 
 #[test]
-fn key_dual_char() {
-    let k: Key = ["-a", "--all"].into();
-    assert_eq!(k.char(), '-')
+fn jargon_arg_option_attached_after_flags() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_attached_after_flags".to_string(),
+        "-ofile".to_string(),
+        "-l".to_string(),
+    ]);
+    j.options(vec!["-o"]);
+
+    assert!(!j.contains("-f"));
+    assert!(!j.contains("-i"));
+    assert!(j.contains("-l"));
+    assert_eq!(j.option_arg("-o"), Some("file".to_string()),);
+    assert!(j.finish().is_empty())
 }
 
 #[test]
-fn key_sub_char() {
-    let k: Key = "beans".into();
-    assert_eq!(k.char(), '\0',)
+fn jargon_contains_option_attached() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_contains_option_attached".to_string(),
+        "-vofile".to_string(),
+    ]);
+    j.options(vec!["-o"]);
+
+    assert!(j.contains("-o"));
+    assert!(j.contains("-v"));
+    assert!(j.finish().is_empty())
 }
 
 #[test]
-fn key_short_text() {
-    let k: Key = "-a".into();
-    assert_eq!(k.text(), "a".to_string(),)
+fn spec_parse_flag_in_value() {
+    let mut j = Jargon::from_vec(vec!["spec_parse_flag_in_value", "-nav"]);
+    let m = spec().parse(&mut j).unwrap();
+
+    assert_eq!(m.result_arg("-n"), Ok("av".to_string()),);
+    assert!(!m.contains("-a"));
+    assert!(!m.contains("-v"));
+    assert!(j.finish().is_empty())
 }
 
 #[test]
-fn key_long_text() {
-    let k: Key = "--all".into();
-    assert_eq!(k.text(), "all".to_string(),)
+fn jargon_arg_option_cluster_missing() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_cluster_missing".to_string(),
+        "-xf".to_string(),
+    ]);
+
+    assert_eq!(j.option_arg::<String, &str>("-f"), None,);
+    assert_eq!(j.finish(), vec!["-xf".to_string()],)
 }
 
 #[test]
-fn key_dual_text() {
-    let k: Key = ["-a", "--all"].into();
-    assert_eq!(k.text(), "all",)
+fn jargon_contains_terminated() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_contains_terminated".to_string(),
+        "--".to_string(),
+        "-v".to_string(),
+    ]);
+
+    assert!(!j.contains("-v"));
+    assert_eq!(j.finish(), vec!["-v".to_string()],)
 }
 
 #[test]
-fn key_sub_text() {
-    let k: Key = "beans".into();
-    assert_eq!(k.text(), "beans",)
+fn jargon_arg_option_terminated() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_arg_option_terminated".to_string(),
+        "-o".to_string(),
+        "--".to_string(),
+        "-o".to_string(),
+        "file".to_string(),
+    ]);
+
+    assert_eq!(j.option_arg::<String, &str>("-o"), None,);
+    assert_eq!(
+        j.finish(),
+        vec!["-o".to_string(), "-o".to_string(), "file".to_string()],
+    )
 }
 
 #[test]
-fn from_env() {
-    let j = Jargon::from_env();
-    assert!(!j.0.is_empty());
+fn jargon_finish_terminated() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_finish_terminated".to_string(),
+        "-v".to_string(),
+        "a".to_string(),
+        "--".to_string(),
+        "-v".to_string(),
+        "--".to_string(),
+    ]);
+
+    assert!(j.contains("-v"));
+    assert_eq!(
+        j.finish(),
+        vec!["a".to_string(), "-v".to_string(), "--".to_string()],
+    )
 }
 
 #[test]
-fn on_sub_v_e() {
-    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "subsub".to_string()]);
-
-    fn subsub(v: Vec<String>) {
-        assert_eq!(v, vec!["subsub".to_string(),])
-    }
+fn sub_terminated() {
+    let mut j: Jargon = Jargon::from_vec(vec![
+        "sub_terminated".to_string(),
+        "--".to_string(),
+        "subsub".to_string(),
+    ]);
 
-    j.on_subcommand("subsub", subsub);
+    assert_eq!(j.subcommand("subsub"), None,)
 }
 
 #[test]
-fn on_sub_v_w() {
+fn on_sub_terminated() {
     let mut j = Jargon::from_vec(vec![
         "on_sub".to_string(),
         "subsub".to_string(),
-        "test".to_string(),
+        "--".to_string(),
+        "-v".to_string(),
     ]);
 
     fn subsub(v: Vec<String>) {
-        assert_eq!(v, vec!["subsub".to_string(), "test".to_string(),])
+        let mut j = Jargon::from_vec(v);
+        assert!(!j.contains("-v"));
+        assert_eq!(j.finish(), vec!["-v".to_string()])
     }
 
     j.on_subcommand("subsub", subsub);
 }
 
 #[test]
-fn on_sub_none() {
-    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "test".to_string()]);
-
-    fn subsub(_: Vec<String>) {
-        panic!()
-    }
+fn jargon_count() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_count",
+        "-vvv",
+        "-v",
+        "file",
+        "--verbose",
+        "-xv",
+    ]);
 
-    j.on_subcommand("subsub", subsub);
+    assert_eq!(j.count(["-v", "--verbose"]), 6,);
+    assert_eq!(j.finish(), vec!["file".to_string(), "-x".to_string()],)
 }
 
 #[test]
-fn on_sub_opt_e() {
-    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "subsub".to_string()]);
-
-    fn subsub(_: Vec<String>) -> Option<u8> {
-        Some(1u8)
-    }
+fn jargon_count_none() {
+    let mut j = Jargon::from_vec(vec!["jargon_count_none", "file", "--", "-v"]);
 
-    assert_eq!(j.opt_on_subcommand("subsub", subsub), Some(1u8),)
+    assert_eq!(j.count("-v"), 0,);
+    assert_eq!(j.finish(), vec!["file".to_string(), "-v".to_string()],)
 }
 
 #[test]
-fn on_sub_opt_w() {
+fn jargon_arg_options() {
     let mut j = Jargon::from_vec(vec![
-        "on_sub".to_string(),
-        "subsub".to_string(),
-        "test".to_string(),
+        "jargon_arg_options",
+        "-I",
+        "include",
+        "file.c",
+        "--include=vendor/include",
+        "-Ilib",
     ]);
 
-    fn subsub(v: Vec<String>) -> Option<u8> {
-        assert_eq!(v, vec!["subsub".to_string(), "test".to_string(),]);
+    assert_eq!(
+        j.option_args::<String, [&str; 2]>(["-I", "--include"]),
+        vec![
+            "include".to_string(),
+            "vendor/include".to_string(),
+            "lib".to_string()
+        ],
+    );
+    assert_eq!(j.finish(), vec!["file.c".to_string()],)
+}
 
-        Some(1u8)
-    }
+#[test]
+fn jargon_arg_options_none() {
+    let mut j = Jargon::from_vec(vec!["jargon_arg_options_none", "file.c"]);
 
-    assert_eq!(j.opt_on_subcommand("subsub", subsub), Some(1u8),)
+    assert!(j.option_args::<String, &str>("-I").is_empty())
 }
 
 #[test]
-fn on_sub_opt_none() {
-    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "test".to_string()]);
+fn jargon_result_args() {
+    let mut j = Jargon::from_vec(vec!["jargon_result_args", "-n", "1", "-n", "2"]);
 
-    fn subsub(_: Vec<String>) -> Option<u8> {
-        panic!()
-    }
-
-    assert_eq!(j.opt_on_subcommand("subsub", subsub), None,)
+    assert_eq!(j.result_args("-n"), Ok(vec![1u8, 2u8]),)
 }
 
 #[test]
-fn on_sub_res_e() {
-    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "subsub".to_string()]);
-
-    fn subsub(_: Vec<String>) -> Result<(), Error> {
-        Ok(())
-    }
+fn jargon_result_args_missing() {
+    let mut j = Jargon::from_vec(vec!["jargon_result_args_missing"]);
 
-    assert_eq!(j.res_on_subcommand("subsub", subsub), Ok(()))
+    assert_eq!(
+        j.result_args::<u8, &str>("-n"),
+        Err(Error::MissingArg("-n".into())),
+    )
 }
 
 #[test]
-fn on_sub_res_none() {
-    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "test".to_string()]);
-
-    fn subsub(_: Vec<String>) -> Result<(), Error> {
-        panic!()
-    }
+fn jargon_result_args_invalid() {
+    let mut j = Jargon::from_vec(vec!["jargon_result_args_invalid", "-n", "1", "-n", "two"]);
 
     assert_eq!(
-        j.res_on_subcommand("subsub", subsub),
-        Err(Error::MissingArg("subsub".into())),
+        j.result_args::<u8, &str>("-n"),
+        Err(Error::InvalidValue {
+            key: "-n".into(),
+            value: "two".to_string(),
+            message: "invalid digit found in string".to_string(),
+            occurrence: Some(2),
+        }),
     )
 }
 
 #[test]
-fn on_sub_res_w() {
-    let mut j = Jargon::from_vec(vec![
-        "on_sub".to_string(),
-        "subsub".to_string(),
-        "test".to_string(),
-    ]);
-
-    fn subsub(_: Vec<String>) -> Result<(), Error> {
-        Ok(())
-    }
+fn jargon_result_args_missing_value() {
+    let mut j = Jargon::from_vec(vec!["jargon_result_args_missing_value", "-n", "1", "-n"]);
 
-    assert_eq!(j.res_on_subcommand("subsub", subsub), Ok(()))
+    assert_eq!(
+        j.result_args::<u8, &str>("-n"),
+        Err(Error::MissingValue("-n".into())),
+    )
 }
 
 #[test]
-fn sub_mut_s_e() {
-    let mut j: Jargon = Jargon::from_vec(vec!["sub_mut_s".to_string(), "subsub".to_string()]);
+fn jargon_result_arg_missing_value() {
+    let mut j = Jargon::from_vec(vec!["jargon_result_arg_missing_value", "--port"]);
 
-    if let Some(v) = j.subcommand("subsub") {
-        assert_eq!(v, vec!["subsub".to_string(),])
-    }
+    assert_eq!(
+        j.result_arg::<u16, &str>("--port"),
+        Err(Error::MissingValue("--port".into())),
+    );
+    assert_eq!(j.finish(), vec!["--port".to_string()],)
 }
 
 #[test]
-fn sub_mut_s_w() {
-    let mut j: Jargon = Jargon::from_vec(vec![
-        "sub_mut_s".to_string(),
-        "subsub".to_string(),
-        "test".to_string(),
-    ]);
+fn jargon_result_arg_invalid() {
+    let mut j = Jargon::from_vec(vec!["jargon_result_arg_invalid", "--port", "abc"]);
 
-    if let Some(v) = j.subcommand("subsub") {
-        assert_eq!(v, vec!["subsub".to_string(), "test".to_string(),])
-    }
+    assert_eq!(
+        j.result_arg::<u16, &str>("--port"),
+        Err(Error::InvalidValue {
+            key: "--port".into(),
+            value: "abc".to_string(),
+            message: "invalid digit found in string".to_string(),
+            occurrence: None,
+        }),
+    )
 }
 
 #[test]
-fn sub_mut_none() {
-    let mut j: Jargon = Jargon::from_vec(vec!["sub_mut_none".to_string(), "test".to_string()]);
+fn jargon_arg_option_invalid() {
+    let mut j = Jargon::from_vec(vec!["jargon_arg_option_invalid", "--port", "abc"]);
 
-    assert_eq!(j.subcommand("subsub"), None,)
+    assert_eq!(j.option_arg::<u16, &str>("--port"), None,);
+    assert!(j.finish().is_empty())
 }
 
 #[test]
-#[cfg(feature = "no_mut")]
+fn jargon_finish_strict() {
+    let mut j = Jargon::from_vec(vec!["jargon_finish_strict", "-v", "-", "file", "--", "-x"]);
+
+    assert!(j.contains("-v"));
+    assert_eq!(
+        j.finish_strict(),
+        Ok(vec!["-".to_string(), "file".to_string(), "-x".to_string()]),
+    )
+}
+
+#[test]
+fn jargon_finish_strict_unknown() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_finish_strict_unknown",
+        "--typo-flag",
+        "file",
+        "-q",
+        "--verbose",
+    ]);
+
+    assert!(j.contains("--verbose"));
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::UnknownArgs(vec![
+            unknown("--typo-flag", None),
+            unknown("-q", None)
+        ])),
+    )
+}
+
+fn unknown(arg: &str, suggestion: Option<&str>) -> Unknown {
+    Unknown {
+        arg: arg.to_string(),
+        suggestion: suggestion.map(|s| s.to_string()),
+    }
+}
+
+#[test]
+fn jargon_finish_strict_suggest() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_finish_strict_suggest",
+        "--verbos",
+        "--colour=always",
+        "--zzz",
+        "-q",
+    ]);
+
+    assert!(!j.contains(["-v", "--verbose"]));
+    assert!(!j.contains("--color"));
+    assert!(!j.contains("-z"));
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::UnknownArgs(vec![
+            unknown("--verbos", Some("--verbose")),
+            unknown("--colour=always", Some("--color")),
+            unknown("--zzz", None),
+            unknown("-q", None),
+        ])),
+    )
+}
+
+#[test]
+fn jargon_finish_strict_subcommand() {
+    let mut j = Jargon::from_vec(vec!["jargon_finish_strict_subcommand", "file.txt"]);
+
+    assert_eq!(j.subcommand("init"), None,);
+    assert_eq!(j.finish_strict(), Ok(vec!["file.txt".to_string()]),)
+}
+
+#[test]
+fn jargon_finish_strict_subcommand_given() {
+    let mut j = Jargon::from_vec(vec!["jargon_finish_strict_subcommand_given", "add", "file"]);
+
+    assert_eq!(j.subcommand("list"), None,);
+    assert!(j.subcommand("add").is_some());
+    assert_eq!(
+        j.finish_strict(),
+        Ok(vec!["add".to_string(), "file".to_string()]),
+    )
+}
+
+#[test]
+fn jargon_finish_strict_spec() {
+    let mut j = Jargon::from_vec(vec!["jargon_finish_strict_spec", "--sufix", ".txt"]);
+
+    spec().parse(&mut j).unwrap();
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::UnknownArgs(vec![unknown(
+            "--sufix",
+            Some("--suffix")
+        )])),
+    )
+}
+
+#[test]
+fn jargon_env_fallback() {
+    let mut j = Jargon::from_vec(vec!["jargon_env_fallback"]);
+
+    j.bind_env(["-r", "--region"], "APP_REGION");
+    j.with_env(vec![("APP_REGION", "eu-west-1")]);
+    assert_eq!(j.option_arg("--region"), Some("eu-west-1".to_string()),);
+    assert_eq!(j.result_arg("-r"), Ok("eu-west-1".to_string()),)
+}
+
+#[test]
+fn jargon_env_fallback_dual() {
+    let mut j = Jargon::from_vec(vec!["jargon_env_fallback_dual"]);
+
+    j.bind_env("--region", "APP_REGION");
+    j.bind_env("-z", "APP_ZONE");
+    j.with_env(vec![("APP_REGION", "eu"), ("APP_ZONE", "b")]);
+    assert_eq!(j.option_arg(["-r", "--region"]), Some("eu".to_string()),);
+    assert_eq!(
+        j.source(["-r", "--region"]),
+        Some(Source::Environment("APP_REGION".to_string())),
+    );
+    assert_eq!(j.result_arg(["-z", "--zone"]), Ok("b".to_string()),)
+}
+
+#[test]
+fn jargon_env_given() {
+    let mut j = Jargon::from_vec(vec!["jargon_env_given", "--region", "us-east-1"]);
+
+    j.bind_env("--region", "APP_REGION");
+    j.with_env(vec![("APP_REGION", "eu-west-1")]);
+    assert_eq!(j.option_arg("--region"), Some("us-east-1".to_string()),)
+}
+
+#[test]
+fn jargon_env_missing() {
+    let mut j = Jargon::from_vec(vec!["jargon_env_missing"]);
+
+    j.bind_env("--region", "APP_REGION");
+    j.with_env(Vec::<(String, String)>::new());
+    assert_eq!(
+        j.result_arg::<String, &str>("--region"),
+        Err(Error::MissingArgEnv {
+            key: "--region".into(),
+            var: "APP_REGION".to_string(),
+        }),
+    );
+    assert_eq!(j.option_arg_os::<OsString, &str>("--region"), None,)
+}
+
+#[test]
+fn jargon_env_process() {
+    let mut j = Jargon::from_vec(vec!["jargon_env_process"]);
+
+    std::env::set_var("JARGON_ENV_PROCESS", "42");
+    j.bind_env("--answer", "JARGON_ENV_PROCESS");
+    assert_eq!(j.result_arg("--answer"), Ok(42u8),)
+}
+
+#[test]
+fn jargon_env_args() {
+    let mut j = Jargon::from_vec(vec!["jargon_env_args"]);
+
+    j.bind_env("-I", "INCLUDE");
+    j.with_env(vec![("INCLUDE", "vendor")]);
+    assert_eq!(j.result_args("-I"), Ok(vec!["vendor".to_string()]),)
+}
+
+#[test]
+fn spec_env() {
+    let spec: Spec = Spec::new("deploy").arg(
+        Arg::option("--region", "REGION")
+            .help("region to deploy to")
+            .default("local")
+            .env("APP_REGION"),
+    );
+    let mut j = Jargon::from_vec(vec!["spec_env"]);
+    j.with_env(vec![("APP_REGION", "eu-west-1")]);
+
+    assert_eq!(
+        spec.parse(&mut j).unwrap().option_arg("--region"),
+        Some("eu-west-1".to_string()),
+    );
+    assert_eq!(
+        spec.help(),
+        "Usage: deploy [OPTION]...
+
+      --region=REGION  region to deploy to (default: local) [env: APP_REGION]
+",
+    )
+}
+
+#[test]
+fn jargon_arg_option_or() {
+    let mut j = Jargon::from_vec(vec!["jargon_arg_option_or", "--port", "8080"]);
+
+    assert_eq!(j.option_arg_or("--port", 80u16), 8080,);
+    assert_eq!(j.source("--port"), Some(Source::CommandLine),);
+    assert_eq!(
+        j.option_arg_or(["-H", "--host"], "localhost".to_string()),
+        "localhost",
+    );
+    assert_eq!(j.source("--host"), Some(Source::Default),);
+    assert_eq!(j.source("--other"), None,);
+    assert_eq!(
+        j.defaults(),
+        vec![
+            ("--port".into(), "80".to_string()),
+            (["-H", "--host"].into(), "localhost".to_string()),
+        ],
+    )
+}
+
+#[test]
+fn jargon_arg_option_or_invalid() {
+    let mut j = Jargon::from_vec(vec!["jargon_arg_option_or_invalid", "--port", "abc"]);
+
+    assert_eq!(j.option_arg_or("--port", 80u16), 80,);
+    assert_eq!(j.source("--port"), Some(Source::CommandLine),)
+}
+
+#[test]
+fn jargon_result_arg_or() {
+    let mut j = Jargon::from_vec(vec![
+        "jargon_result_arg_or",
+        "--port",
+        "abc",
+        "--jobs",
+        "2",
+        "-q",
+    ]);
+
+    assert_eq!(j.result_arg_or("--jobs", 1u8), Ok(2),);
+    assert_eq!(
+        j.result_arg_or(["-H", "--host"], "localhost".to_string()),
+        Ok("localhost".to_string()),
+    );
+    assert_eq!(j.source("--host"), Some(Source::Default),);
+    assert_eq!(
+        j.result_arg_or("--port", 80u16),
+        Err(Error::InvalidValue {
+            key: "--port".into(),
+            value: "abc".to_string(),
+            message: "invalid digit found in string".to_string(),
+            occurrence: None,
+        }),
+    );
+    assert_eq!(j.source("--port"), Some(Source::CommandLine),);
+    assert_eq!(
+        j.result_arg_or("-q", 0u8),
+        Err(Error::MissingValue("-q".into())),
+    )
+}
+
+#[test]
+fn jargon_arg_option_or_else() {
+    let mut j = Jargon::from_vec(vec!["jargon_arg_option_or_else", "--jobs", "2"]);
+
+    assert_eq!(j.option_arg_or_else("--jobs", || -> u8 { panic!() }), 2,);
+    assert_eq!(j.option_arg_or_else("--threads", || 4u8), 4,);
+    assert_eq!(j.source("--threads"), Some(Source::Default),);
+    assert_eq!(j.defaults(), vec![("--threads".into(), "4".to_string())],)
+}
+
+#[test]
+fn jargon_arg_option_or_env() {
+    let mut j = Jargon::from_vec(vec!["jargon_arg_option_or_env"]);
+
+    j.bind_env("--region", "APP_REGION");
+    j.with_env(vec![("APP_REGION", "eu-west-1")]);
+    assert_eq!(
+        j.option_arg_or("--region", "local".to_string()),
+        "eu-west-1",
+    );
+    assert_eq!(
+        j.source("--region"),
+        Some(Source::Environment("APP_REGION".to_string())),
+    )
+}
+
+#[test]
+fn spec_defaults() {
+    let spec: Spec = Spec::new("serve").arg(Arg::option("--port", "PORT").help("port to use"));
+    let mut j = Jargon::from_vec(vec!["spec_defaults"]);
+    j.option_arg_or("--port", 80u16);
+
+    assert_eq!(
+        spec.defaults(&j).help(),
+        "Usage: serve [OPTION]...
+
+      --port=PORT  port to use (default: 80)
+",
+    )
+}
+
+#[test]
+fn spec_source() {
+    let mut j = Jargon::from_vec(vec!["spec_source", "-a"]);
+    j.with_env(Vec::<(String, String)>::new());
+    let m = spec().parse(&mut j).unwrap();
+
+    assert_eq!(m.source("-a"), Some(Source::CommandLine),);
+    assert_eq!(m.source("-n"), Some(Source::Default),);
+    assert_eq!(m.source("--suffix"), None,)
+}
+
+#[cfg(unix)]
+fn non_utf8(prefix: &str) -> OsString {
+    use std::os::unix::ffi::OsStringExt;
+    let mut v: Vec<u8> = prefix.as_bytes().to_vec();
+    v.extend_from_slice(b"\xffname");
+    OsString::from_vec(v)
+}
+
+#[test]
+fn jargon_from_vec_os() {
+    let j = Jargon::from_vec_os(vec!["jargon_from_vec_os", "-a"]);
+
+    assert_eq!(j, Jargon::from_vec(vec!["jargon_from_vec_os", "-a"]),)
+}
+
+#[test]
+fn jargon_arg_option_os() {
+    let mut j = Jargon::from_vec(vec!["jargon_arg_option_os", "--output", "out.json"]);
+
+    assert_eq!(
+        j.option_arg_os(["-o", "--output"]),
+        Some(PathBuf::from("out.json")),
+    )
+}
+
+#[test]
+fn jargon_result_arg_os_missing() {
+    let mut j = Jargon::from_vec(vec!["jargon_result_arg_os_missing"]);
+
+    assert_eq!(
+        j.result_arg_os::<OsString, &str>("--output"),
+        Err(Error::MissingArg("--output".into())),
+    )
+}
+
+#[test]
+#[cfg(unix)]
+fn jargon_arg_option_os_non_utf8() {
+    let mut j = Jargon::from_vec_os(vec![
+        OsString::from("jargon_arg_option_os_non_utf8"),
+        OsString::from("-o"),
+        non_utf8(""),
+        OsString::from("--input"),
+        non_utf8(""),
+    ]);
+
+    assert_eq!(j.option_arg_os::<OsString, &str>("-o"), Some(non_utf8("")),);
+    assert_eq!(j.option_arg::<String, &str>("--input"), None,);
+    assert!(j.finish_os::<OsString>().is_empty())
+}
+
+#[test]
+#[cfg(unix)]
+fn jargon_arg_option_os_non_utf8_attached() {
+    let mut j = Jargon::from_vec_os(vec![
+        OsString::from("jargon_arg_option_os_non_utf8_attached"),
+        non_utf8("--output="),
+        non_utf8("-xo"),
+    ]);
+
+    assert_eq!(
+        j.option_arg_os::<OsString, &str>("--output"),
+        Some(non_utf8("")),
+    );
+    assert_eq!(j.option_arg_os::<OsString, &str>("-o"), Some(non_utf8("")),);
+    assert_eq!(j.finish(), vec!["-x".to_string()],)
+}
+
+#[test]
+#[cfg(unix)]
+fn jargon_contains_non_utf8() {
+    let mut j = Jargon::from_vec_os(vec![
+        OsString::from("jargon_contains_non_utf8"),
+        non_utf8("-v"),
+        non_utf8(""),
+    ]);
+
+    assert!(j.contains("-v"));
+    assert!(!j.contains("-n"));
+    assert_eq!(j.finish_os::<OsString>(), vec![non_utf8("-"), non_utf8("")],)
+}
+
+#[test]
+fn key_short_is_short() {
+    let k: Key = "-a".into();
+    assert!(k.is_short())
+}
+
+#[test]
+fn key_short_is_long() {
+    let k: Key = "-a".into();
+    assert!(!k.is_long())
+}
+
+#[test]
+fn key_short_is_dual() {
+    let k: Key = "-a".into();
+    assert!(!k.is_dual())
+}
+
+#[test]
+fn key_short_is_sub() {
+    let k: Key = "-a".into();
+    assert!(!k.is_sub())
+}
+
+#[test]
+fn key_long_is_short() {
+    let k: Key = "--all".into();
+    assert!(!k.is_short())
+}
+
+#[test]
+fn key_long_is_long() {
+    let k: Key = "--all".into();
+    assert!(k.is_long())
+}
+
+#[test]
+fn key_long_is_dual() {
+    let k: Key = "--all".into();
+    assert!(!k.is_dual())
+}
+
+#[test]
+fn key_long_is_sub() {
+    let k: Key = "--all".into();
+    assert!(!k.is_sub())
+}
+
+#[test]
+fn key_dual_is_short() {
+    let k: Key = ["-a", "--all"].into();
+    assert!(!k.is_short())
+}
+
+#[test]
+fn key_dual_is_long() {
+    let k: Key = ["-a", "--all"].into();
+    assert!(!k.is_long())
+}
+
+#[test]
+fn key_dual_is_dual() {
+    let k: Key = ["-a", "--all"].into();
+    assert!(k.is_dual())
+}
+
+#[test]
+fn key_dual_is_sub() {
+    let k: Key = ["-a", "--all"].into();
+    assert!(!k.is_sub())
+}
+
+#[test]
+fn key_sub_is_short() {
+    let k: Key = "subcmd".into();
+    assert!(!k.is_short())
+}
+
+#[test]
+fn key_sub_is_long() {
+    let k: Key = "subcmd".into();
+    assert!(!k.is_long())
+}
+
+#[test]
+fn key_sub_is_dual() {
+    let k: Key = "subcmd".into();
+    assert!(!k.is_dual())
+}
+
+#[test]
+fn key_sub_is_sub() {
+    let k: Key = "subcmd".into();
+    assert!(k.is_sub())
+}
+
+#[test]
+fn key_short_char() {
+    let k: Key = "-a".into();
+    assert_eq!(k.char(), '-',)
+}
+
+#[test]
+fn key_long_char() {
+    let k: Key = "--all".into();
+    assert_eq!(k.char(), '-')
+}
+
+#[test]
+fn key_dual_char() {
+    let k: Key = ["-a", "--all"].into();
+    assert_eq!(k.char(), '-')
+}
+
+#[test]
+fn key_sub_char() {
+    let k: Key = "beans".into();
+    assert_eq!(k.char(), '\0',)
+}
+
+#[test]
+fn key_short_text() {
+    let k: Key = "-a".into();
+    assert_eq!(k.text(), "a".to_string(),)
+}
+
+#[test]
+fn key_long_text() {
+    let k: Key = "--all".into();
+    assert_eq!(k.text(), "all".to_string(),)
+}
+
+#[test]
+fn key_dual_text() {
+    let k: Key = ["-a", "--all"].into();
+    assert_eq!(k.text(), "all",)
+}
+
+#[test]
+fn key_sub_text() {
+    let k: Key = "beans".into();
+    assert_eq!(k.text(), "beans",)
+}
+
+#[test]
+fn from_env() {
+    let j = Jargon::from_env();
+    assert!(!j.0.is_empty());
+}
+
+#[test]
+fn on_sub_v_e() {
+    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "subsub".to_string()]);
+
+    fn subsub(v: Vec<String>) {
+        assert_eq!(v, vec!["subsub".to_string(),])
+    }
+
+    j.on_subcommand("subsub", subsub);
+}
+
+#[test]
+fn on_sub_v_w() {
+    let mut j = Jargon::from_vec(vec![
+        "on_sub".to_string(),
+        "subsub".to_string(),
+        "test".to_string(),
+    ]);
+
+    fn subsub(v: Vec<String>) {
+        assert_eq!(v, vec!["subsub".to_string(), "test".to_string(),])
+    }
+
+    j.on_subcommand("subsub", subsub);
+}
+
+#[test]
+fn on_sub_none() {
+    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "test".to_string()]);
+
+    fn subsub(_: Vec<String>) {
+        panic!()
+    }
+
+    j.on_subcommand("subsub", subsub);
+}
+
+#[test]
+fn on_sub_opt_e() {
+    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "subsub".to_string()]);
+
+    fn subsub(_: Vec<String>) -> Option<u8> {
+        Some(1u8)
+    }
+
+    assert_eq!(j.opt_on_subcommand("subsub", subsub), Some(1u8),)
+}
+
+#[test]
+fn on_sub_opt_w() {
+    let mut j = Jargon::from_vec(vec![
+        "on_sub".to_string(),
+        "subsub".to_string(),
+        "test".to_string(),
+    ]);
+
+    fn subsub(v: Vec<String>) -> Option<u8> {
+        assert_eq!(v, vec!["subsub".to_string(), "test".to_string(),]);
+
+        Some(1u8)
+    }
+
+    assert_eq!(j.opt_on_subcommand("subsub", subsub), Some(1u8),)
+}
+
+#[test]
+fn on_sub_opt_none() {
+    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "test".to_string()]);
+
+    fn subsub(_: Vec<String>) -> Option<u8> {
+        panic!()
+    }
+
+    assert_eq!(j.opt_on_subcommand("subsub", subsub), None,)
+}
+
+#[test]
+fn on_sub_res_e() {
+    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "subsub".to_string()]);
+
+    fn subsub(_: Vec<String>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    assert_eq!(j.res_on_subcommand("subsub", subsub), Ok(()))
+}
+
+#[test]
+fn on_sub_res_none() {
+    let mut j = Jargon::from_vec(vec!["on_sub".to_string(), "test".to_string()]);
+
+    fn subsub(_: Vec<String>) -> Result<(), Error> {
+        panic!()
+    }
+
+    assert_eq!(
+        j.res_on_subcommand("subsub", subsub),
+        Err(Error::MissingArg("subsub".into())),
+    )
+}
+
+#[test]
+fn on_sub_res_w() {
+    let mut j = Jargon::from_vec(vec![
+        "on_sub".to_string(),
+        "subsub".to_string(),
+        "test".to_string(),
+    ]);
+
+    fn subsub(_: Vec<String>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    assert_eq!(j.res_on_subcommand("subsub", subsub), Ok(()))
+}
+
+#[test]
+fn sub_mut_s_e() {
+    let mut j: Jargon = Jargon::from_vec(vec!["sub_mut_s".to_string(), "subsub".to_string()]);
+
+    if let Some(v) = j.subcommand("subsub") {
+        assert_eq!(v, vec!["subsub".to_string(),])
+    }
+}
+
+#[test]
+fn sub_mut_s_w() {
+    let mut j: Jargon = Jargon::from_vec(vec![
+        "sub_mut_s".to_string(),
+        "subsub".to_string(),
+        "test".to_string(),
+    ]);
+
+    if let Some(v) = j.subcommand("subsub") {
+        assert_eq!(v, vec!["subsub".to_string(), "test".to_string(),])
+    }
+}
+
+#[test]
+fn sub_mut_none() {
+    let mut j: Jargon = Jargon::from_vec(vec!["sub_mut_none".to_string(), "test".to_string()]);
+
+    assert_eq!(j.subcommand("subsub"), None,)
+}
+
+#[test]
+#[cfg(feature = "no_mut")]
 fn sub_s_e() {
     let j: Jargon = Jargon::from_vec(vec!["sub_mut_s".to_string(), "subsub".to_string()]);
 
@@ -939,23 +1754,984 @@ fn sub_s_e() {
 }
 
 #[test]
-fn sub_s_w() {
-    let mut j: Jargon = Jargon::from_vec(vec![
-        "sub_mut_s".to_string(),
-        "subsub".to_string(),
-        "test".to_string(),
+fn sub_s_w() {
+    let mut j: Jargon = Jargon::from_vec(vec![
+        "sub_mut_s".to_string(),
+        "subsub".to_string(),
+        "test".to_string(),
+    ]);
+
+    if let Some(v) = j.subcommand("subsub") {
+        assert_eq!(v, vec!["subsub".to_string(), "test".to_string(),])
+    }
+}
+
+#[test]
+fn sub_none() {
+    let mut j: Jargon = Jargon::from_vec(vec!["sub_mut_none".to_string(), "test".to_string()]);
+
+    assert_eq!(j.subcommand("subsub"), None,)
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    List(Vec<String>),
+    Remove(usize),
+}
+
+fn dispatch(j: &mut Jargon) -> Result<Command, Error> {
+    j.dispatch()
+        .on("list", |args| Ok(Command::List(args)))
+        .on("remove", |args| {
+            let mut j: Jargon = Jargon::from_vec(args);
+            j.result_arg("--index").map(Command::Remove)
+        })
+        .run()
+}
+
+#[test]
+fn dispatch_first() {
+    let mut j: Jargon = Jargon::from_vec(vec!["dispatch_first", "-v", "list", "remove"]);
+
+    assert_eq!(
+        dispatch(&mut j),
+        Ok(Command::List(vec![
+            "-v".to_string(),
+            "list".to_string(),
+            "remove".to_string(),
+        ])),
+    )
+}
+
+#[test]
+fn dispatch_positional() {
+    let mut j: Jargon = Jargon::from_vec(vec!["dispatch_positional", "--name", "list", "remove"]);
+
+    assert_eq!(j.option_arg("--name"), Some("list".to_string()),);
+    assert_eq!(dispatch(&mut j), Err(Error::MissingArg("--index".into())),);
+
+    let mut j: Jargon = Jargon::from_vec(vec!["dispatch_positional", "file.txt", "list"]);
+    assert_eq!(
+        dispatch(&mut j),
+        Err(Error::UnknownSubcommand {
+            arg: Some("file.txt".to_string()),
+            suggestion: None,
+            expected: vec!["list".into(), "remove".into()],
+        }),
+    )
+}
+
+#[test]
+#[cfg(unix)]
+fn dispatch_non_utf8() {
+    let mut j: Jargon =
+        Jargon::from_vec_os(vec![OsString::from("dispatch_non_utf8"), non_utf8("list")]);
+
+    assert_eq!(
+        dispatch(&mut j),
+        Err(Error::UnknownSubcommand {
+            arg: Some("list\u{FFFD}name".to_string()),
+            suggestion: Some("list".to_string()),
+            expected: vec!["list".into(), "remove".into()],
+        }),
+    )
+}
+
+#[test]
+fn dispatch_handler_error() {
+    let mut j: Jargon = Jargon::from_vec(vec!["dispatch_handler_error", "remove", "--index=x"]);
+
+    assert_eq!(
+        dispatch(&mut j),
+        Err(Error::InvalidValue {
+            key: "--index".into(),
+            value: "x".to_string(),
+            message: "invalid digit found in string".to_string(),
+            occurrence: None,
+        }),
+    );
+
+    let mut j: Jargon = Jargon::from_vec(vec!["dispatch_handler_error", "remove", "--index=2"]);
+
+    assert_eq!(dispatch(&mut j), Ok(Command::Remove(2)),)
+}
+
+#[test]
+fn dispatch_unknown() {
+    let mut j: Jargon = Jargon::from_vec(vec!["dispatch_unknown", "-v", "lsit"]);
+
+    assert_eq!(
+        dispatch(&mut j),
+        Err(Error::UnknownSubcommand {
+            arg: Some("lsit".to_string()),
+            suggestion: Some("list".to_string()),
+            expected: vec!["list".into(), "remove".into()],
+        }),
+    );
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::UnknownArgs(vec![unknown("-v", None)])),
+    )
+}
+
+#[test]
+fn dispatch_missing() {
+    let mut j: Jargon = Jargon::from_vec(vec!["dispatch_missing", "--", "list"]);
+
+    assert_eq!(
+        dispatch(&mut j),
+        Err(Error::UnknownSubcommand {
+            arg: None,
+            suggestion: None,
+            expected: vec!["list".into(), "remove".into()],
+        }),
+    )
+}
+
+#[test]
+fn positional_subcommand_value() {
+    let mut j: Jargon = Jargon::from_vec(vec!["positional_subcommand_value", "--name", "list"]);
+    j.positional_subcommands();
+
+    assert_eq!(j.option_arg("--name"), Some("list".to_string()),);
+    assert_eq!(j.subcommand("list"), None,);
+
+    let mut j: Jargon = Jargon::from_vec(vec!["positional_subcommand_value", "--name", "list"]);
+    j.positional_subcommands();
+
+    assert_eq!(j.subcommand("list"), Some(vec!["list".to_string()]),)
+}
+
+#[test]
+fn positional_subcommand_args() {
+    let mut j: Jargon = Jargon::from_vec(vec![
+        "positional_subcommand_args",
+        "-v",
+        "list",
+        "--all",
+        "remove",
+        "--",
+        "-v",
+    ]);
+    j.positional_subcommands();
+
+    assert_eq!(j.subcommand("remove"), None,);
+    assert_eq!(
+        j.subcommand("list"),
+        Some(vec![
+            "list".to_string(),
+            "--all".to_string(),
+            "remove".to_string(),
+            "--".to_string(),
+            "-v".to_string(),
+        ]),
+    );
+    assert!(!j.contains("--all"));
+    assert!(j.contains("-v"));
+    assert_eq!(j.finish_strict(), Ok(Vec::new()),)
+}
+
+#[test]
+fn positional_subcommand_dispatch() {
+    let mut j: Jargon = Jargon::from_vec(vec![
+        "positional_subcommand_dispatch",
+        "--verbose",
+        "remove",
+        "--index",
+        "3",
+        "list",
+    ]);
+    j.positional_subcommands();
+
+    assert_eq!(dispatch(&mut j), Ok(Command::Remove(3)),);
+    assert!(j.contains("--verbose"));
+    assert!(j.finish().is_empty())
+}
+
+#[test]
+fn positional_subcommand_unknown() {
+    let mut j: Jargon = Jargon::from_vec(vec!["positional_subcommand_unknown", "-x", "a", "list"]);
+    j.positional_subcommands();
+
+    assert_eq!(
+        dispatch(&mut j),
+        Err(Error::UnknownSubcommand {
+            arg: Some("a".to_string()),
+            suggestion: None,
+            expected: vec!["list".into(), "remove".into()],
+        }),
+    )
+}
+
+#[test]
+fn subcommand_aliases() {
+    let mut j: Jargon = Jargon::from_vec(vec!["subcommand_aliases", "-v", "del", "3"]);
+    j.aliases("remove", vec!["rm", "del"]);
+
+    assert_eq!(j.subcommand("rm"), None,);
+    assert_eq!(
+        j.subcommand("remove"),
+        Some(vec!["-v".to_string(), "del".to_string(), "3".to_string()]),
+    );
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::UnknownArgs(vec![unknown("-v", None)])),
+    )
+}
+
+#[test]
+fn subcommand_aliases_dispatch() {
+    let mut j: Jargon = Jargon::from_vec(vec!["subcommand_aliases_dispatch", "rm", "--index=1"]);
+    j.positional_subcommands();
+    j.aliases("remove", vec!["rm"]);
+
+    assert_eq!(dispatch(&mut j), Ok(Command::Remove(1)),)
+}
+
+#[test]
+fn subcommand_prefixes() {
+    let mut j: Jargon = Jargon::from_vec(vec!["subcommand_prefixes", "rem", "--index", "4"]);
+    j.positional_subcommands();
+    j.subcommand_prefixes(vec!["list", "listen", "remove"]);
+
+    assert_eq!(dispatch(&mut j), Ok(Command::Remove(4)),);
+
+    let mut j: Jargon = Jargon::from_vec(vec!["subcommand_prefixes", "list"]);
+    j.subcommand_prefixes(vec!["list", "listen", "remove"]);
+
+    assert_eq!(j.subcommand("listen"), None,);
+    assert_eq!(j.subcommand("list"), Some(vec!["list".to_string()]),)
+}
+
+#[test]
+fn subcommand_prefixes_aliases() {
+    let mut j: Jargon = Jargon::from_vec(vec!["subcommand_prefixes_aliases", "de"]);
+    j.aliases("remove", vec!["rm", "delete"]);
+    j.subcommand_prefixes(vec!["remove", "list"]);
+
+    assert_eq!(j.subcommand("remove"), Some(vec!["de".to_string()]),)
+}
+
+#[test]
+fn subcommand_prefixes_ambiguous() {
+    let mut j: Jargon = Jargon::from_vec(vec!["subcommand_prefixes_ambiguous", "li"]);
+    j.subcommand_prefixes(vec!["list", "listen", "remove"]);
+
+    assert_eq!(j.subcommand("remove"), None,);
+    assert_eq!(j.subcommand("list"), None,);
+    assert_eq!(
+        dispatch(&mut j),
+        Err(Error::Ambiguous {
+            arg: "li".to_string(),
+            candidates: vec!["list".into(), "listen".into()],
+        }),
+    )
+}
+
+fn context() -> Context {
+    Context::new()
+        .global(Arg::flag(["-v", "--verbose"]))
+        .global(Arg::option("--color", "WHEN").env("APP_COLOR"))
+}
+
+#[test]
+fn context_nested() {
+    let mut ctx: Context = context();
+    let mut j: Jargon = Jargon::from_vec(vec![
+        "context_nested",
+        "-v",
+        "--color=never",
+        "go",
+        "--verbose",
+        "goo",
+        "-x",
+    ]);
+    j.positional_subcommands();
+
+    let v = j.ctx_on_subcommand(&mut ctx, "go", |args, ctx| {
+        assert_eq!(ctx.count("-v"), 2,);
+        let mut j: Jargon = Jargon::from_vec(args);
+        j.positional_subcommands();
+        j.ctx_on_subcommand(ctx, "goo", |args, _| Ok(args))
+    });
+
+    assert_eq!(v, Ok(vec!["goo".to_string(), "-x".to_string()]),);
+    assert_eq!(ctx.count("--verbose"), 2,);
+    assert_eq!(ctx.option_arg("--color"), Some("never".to_string()),);
+    assert!(j.finish().is_empty())
+}
+
+#[test]
+fn context_levels() {
+    let mut ctx: Context = context();
+    let mut j: Jargon = Jargon::from_vec(vec!["context_levels", "go"]);
+    j.with_env(vec![("APP_COLOR", "always")]);
+    ctx.parse(&mut j).unwrap();
+
+    assert_eq!(ctx.option_arg("--color"), Some("always".to_string()),);
+    assert_eq!(
+        ctx.source("--color"),
+        Some(Source::Environment("APP_COLOR".to_string())),
+    );
+
+    let mut j: Jargon = Jargon::from_vec(vec!["go", "--color", "never", "-v"]);
+    ctx.parse(&mut j).unwrap();
+
+    assert_eq!(
+        ctx.option_args::<String, &str>("--color"),
+        vec!["never".to_string()],
+    );
+    assert_eq!(ctx.source("--color"), Some(Source::CommandLine),);
+    assert!(ctx.contains("--verbose"));
+    assert!(j.finish().is_empty())
+}
+
+#[test]
+fn context_missing() {
+    let mut ctx: Context = context();
+    let mut j: Jargon = Jargon::from_vec(vec!["context_missing", "-v", "stop"]);
+
+    assert_eq!(
+        j.ctx_on_subcommand(&mut ctx, "go", |args, _| Ok(args)),
+        Err(Error::MissingArg("go".into())),
+    );
+    assert!(ctx.contains("-v"));
+
+    let mut j: Jargon = Jargon::from_vec(vec!["context_missing", "go", "--color"]);
+
+    assert_eq!(
+        j.ctx_on_subcommand(&mut ctx, "go", |args, _| Ok(args)),
+        Err(Error::MissingValue("--color".into())),
+    )
+}
+
+#[test]
+fn long_prefixes() {
+    let mut j: Jargon = Jargon::from_vec(vec![
+        "long_prefixes",
+        "--verb",
+        "--out",
+        "a.txt",
+        "--ver",
+        "--col=never",
+    ]);
+    j.long_prefixes(vec!["--verbose", "--version", "--output", "--color"]);
+
+    assert_eq!(j.option_arg(["-o", "--output"]), Some("a.txt".to_string()),);
+    assert_eq!(j.option_arg("--color"), Some("never".to_string()),);
+    assert!(j.contains(["-v", "--verbose"]));
+    assert!(!j.contains("--version"));
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::Ambiguous {
+            arg: "--ver".to_string(),
+            candidates: vec!["--verbose".into(), "--version".into()],
+        }),
+    )
+}
+
+#[test]
+fn long_prefixes_exact() {
+    let mut j: Jargon = Jargon::from_vec(vec!["long_prefixes_exact", "--all", "--alm"]);
+    j.long_prefixes(vec!["--all", "--almost-all"]);
+
+    assert_eq!(j.count("--almost-all"), 1,);
+    assert!(j.contains("--all"));
+    assert!(j.finish().is_empty())
+}
+
+#[test]
+fn long_prefixes_ambiguous() {
+    let mut j: Jargon = Jargon::from_vec(vec!["long_prefixes_ambiguous", "--ver=2"]);
+    j.long_prefixes(vec!["--verbose", "--version"]);
+
+    assert_eq!(j.option_arg::<u8, &str>("--verbose"), None,);
+    assert_eq!(
+        j.result_arg::<u8, &str>("--version"),
+        Err(Error::Ambiguous {
+            arg: "--ver".to_string(),
+            candidates: vec!["--verbose".into(), "--version".into()],
+        }),
+    )
+}
+
+#[test]
+fn long_prefixes_off() {
+    let mut j: Jargon = Jargon::from_vec(vec!["long_prefixes_off", "--verb"]);
+
+    assert!(!j.contains("--verbose"));
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::UnknownArgs(vec![unknown("--verb", None)])),
+    )
+}
+
+fn temp_files(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("jargon_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(dir.join("nested")).unwrap();
+    for (file, text) in files {
+        std::fs::write(dir.join(file), text).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn response_files() {
+    let dir = temp_files(
+        "response_files",
+        &[
+            (
+                "args",
+                "# build flags\n-o \"out dir/a.out\" -D'X=1 2'\n@nested/more '@kept' \\@also\n",
+            ),
+            ("nested/more", "-Wall\\ -Wextra   # the warnings\n-O2\n"),
+        ],
+    );
+    let file = format!("@{}", dir.join("args").display());
+    let mut j: Jargon = Jargon::from_vec(vec!["cc", &file, "main.c", "--", &file]);
+    j.response_files().unwrap();
+
+    assert_eq!(
+        j,
+        Jargon::from_vec(vec![
+            "cc",
+            "-o",
+            "out dir/a.out",
+            "-DX=1 2",
+            "-Wall -Wextra",
+            "-O2",
+            "@kept",
+            "@also",
+            "main.c",
+            "--",
+            &file,
+        ]),
+    )
+}
+
+#[test]
+fn response_files_cycle() {
+    let dir = temp_files(
+        "response_files_cycle",
+        &[("a", "-x @b\n"), ("b", "-y\n\n@a\n")],
+    );
+    let file = format!("@{}", dir.join("a").display());
+    let mut j: Jargon = Jargon::from_vec(vec!["cc", &file]);
+
+    assert_eq!(
+        j.response_files(),
+        Err(Error::ResponseFile {
+            file: dir.join("b").display().to_string(),
+            line: Some(3),
+            message: format!("'{}' includes itself", dir.join("a").display()),
+        }),
+    );
+    assert_eq!(j, Jargon::from_vec(vec!["cc", &file]))
+}
+
+#[test]
+fn response_files_unterminated() {
+    let dir = temp_files(
+        "response_files_unterminated",
+        &[("args", "-a\n-b 'one\ntwo\n")],
+    );
+    let file = dir.join("args");
+    let mut j: Jargon = Jargon::from_vec(vec!["cc".to_string(), format!("@{}", file.display())]);
+
+    assert_eq!(
+        j.response_files(),
+        Err(Error::ResponseFile {
+            file: file.display().to_string(),
+            line: Some(2),
+            message: "unterminated single quote".to_string(),
+        }),
+    )
+}
+
+#[test]
+fn response_files_missing() {
+    let dir = temp_files("response_files_missing", &[("args", "-a\n@missing\n")]);
+    let mut j: Jargon = Jargon::from_vec(vec!["cc", "@", "-@"]);
+    j.response_files().unwrap();
+    assert_eq!(j.finish(), vec!["@", "-@"]);
+
+    let mut j: Jargon = Jargon::from_vec(vec![
+        "cc".to_string(),
+        format!("@{}", dir.join("args").display()),
+    ]);
+    match j.response_files() {
+        Err(Error::ResponseFile {
+            file,
+            line: Some(2),
+            message,
+        }) => {
+            assert_eq!(file, dir.join("args").display().to_string());
+            assert!(message.starts_with(&format!(
+                "cannot read '{}': ",
+                dir.join("missing").display()
+            )));
+        }
+        e => panic!("{:?}", e),
+    }
+}
+
+#[test]
+fn config_files() {
+    let dir = temp_files(
+        "config_files",
+        &[
+            (
+                "nested/config",
+                "# defaults\ncolor = never\nregion = 'us-east-1'\n--width = \"80\"\nverbose = 2\nall = off\n",
+            ),
+            ("override", "color = always\n"),
+        ],
+    );
+    let mut j: Jargon = Jargon::from_vec(vec!["config_files", "--width", "120", "-v"]);
+    j.bind_env("--region", "APP_REGION");
+    j.with_env(vec![("XDG_CONFIG_HOME", dir.display().to_string())]);
+    j.config_files("nested").unwrap();
+
+    assert_eq!(j.option_arg("--color"), Some("never".to_string()),);
+    assert_eq!(
+        j.source("--color"),
+        Some(Source::Config(dir.join("nested").join("config"))),
+    );
+    assert_eq!(j.option_arg("--region"), Some("us-east-1".to_string()),);
+    assert_eq!(j.option_arg("--width"), Some(120),);
+    assert_eq!(j.source("--width"), Some(Source::CommandLine),);
+    assert_eq!(j.count(["-v", "--verbose"]), 1,);
+    assert!(!j.contains(["-a", "--all"]));
+
+    j.config_file(dir.join("override")).unwrap();
+    assert_eq!(j.option_arg("--color"), Some("always".to_string()),);
+    assert_eq!(
+        j.source("--color"),
+        Some(Source::Config(dir.join("override"))),
+    );
+
+    let mut j: Jargon = Jargon::from_vec(vec!["config_files"]);
+    j.bind_env("--region", "APP_REGION");
+    j.with_env(vec![
+        ("HOME", dir.display().to_string()),
+        ("APP_REGION", "eu-west-1".to_string()),
+    ]);
+    j.config_files("nested").unwrap();
+    assert_eq!(j.option_arg("--region"), Some("eu-west-1".to_string()),);
+    assert_eq!(j.option_arg::<String, &str>("--color"), None,);
+    assert_eq!(j.count(["-v", "--verbose"]), 0,)
+}
+
+#[test]
+fn config_spec() {
+    let dir = temp_files(
+        "config_spec",
+        &[("config", "suffix = .txt\nmultiple = true\n")],
+    );
+    let mut j: Jargon = Jargon::from_vec(vec!["config_spec", "--zero"]);
+    j.config_file(dir.join("config")).unwrap();
+    let m = spec().parse(&mut j).unwrap();
+
+    assert!(m.contains("-a"));
+    assert_eq!(m.source("-a"), Some(Source::Config(dir.join("config"))),);
+    assert_eq!(m.option_arg("--suffix"), Some(".txt".to_string()),);
+    assert_eq!(
+        m.source("--suffix"),
+        Some(Source::Config(dir.join("config"))),
+    );
+    assert_eq!(m.source("--zero"), Some(Source::CommandLine),);
+    assert_eq!(m.source("-n"), Some(Source::Default),);
+
+    let mut ctx: Context = Context::new().global(Arg::option("--color", "WHEN"));
+    let mut j: Jargon = Jargon::from_vec(vec!["config_spec"]);
+    std::fs::write(dir.join("color"), "color = never\n").unwrap();
+    j.config_file(dir.join("color")).unwrap();
+    ctx.parse(&mut j).unwrap();
+    assert_eq!(
+        ctx.source("--color"),
+        Some(Source::Config(dir.join("color"))),
+    );
+
+    let mut j: Jargon = Jargon::from_vec(vec!["config_spec", "--color", "always"]);
+    ctx.parse(&mut j).unwrap();
+    assert_eq!(
+        ctx.option_args::<String, &str>("--color"),
+        vec!["always".to_string()],
+    );
+    assert_eq!(ctx.source("--color"), Some(Source::CommandLine),)
+}
+
+#[test]
+fn config_errors() {
+    let dir = temp_files(
+        "config_errors",
+        &[("config", "color = never\n\n[section]\n")],
+    );
+    let mut j: Jargon = Jargon::from_vec(vec!["config_errors"]);
+
+    assert_eq!(
+        j.config_file(dir.join("config")),
+        Err(Error::Config {
+            file: dir.join("config").display().to_string(),
+            line: Some(3),
+            message: "expected 'key = value', found '[section]'".to_string(),
+        }),
+    );
+    assert!(j.config_file(dir.join("missing")).is_err());
+    assert_eq!(j.config_files("config_errors_missing"), Ok(()),)
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn config_toml() {
+    let dir = temp_files(
+        "config_toml",
+        &[
+            (
+                "config.toml",
+                "color = \"never\"\nwidth = 80\nverbose = true\n",
+            ),
+            ("bad.toml", "color = \"never\"\nwidth = \n"),
+            ("table.toml", "[remote]\nurl = \"x\"\n"),
+        ],
+    );
+    let mut j: Jargon = Jargon::from_vec(vec!["config_toml"]);
+    j.config_file(dir.join("config.toml")).unwrap();
+
+    assert_eq!(j.option_arg("--color"), Some("never".to_string()),);
+    assert_eq!(j.option_arg("--width"), Some(80),);
+    assert!(j.contains("--verbose"));
+
+    match j.config_file(dir.join("bad.toml")) {
+        Err(Error::Config { line, .. }) => assert_eq!(line, Some(2),),
+        e => panic!("{:?}", e),
+    }
+    assert_eq!(
+        j.config_file(dir.join("table.toml")),
+        Err(Error::Config {
+            file: dir.join("table.toml").display().to_string(),
+            line: None,
+            message: "'remote' must be a string, number or boolean".to_string(),
+        }),
+    )
+}
+
+#[test]
+fn exclusive() {
+    let mut j: Jargon = Jargon::from_vec(vec!["exclusive", "--json", "-v", "--json"]);
+    j.exclusive(vec!["--json", "--yaml", "--table"]);
+
+    assert!(j.contains("--json"));
+    assert!(!j.contains("--yaml"));
+    assert_eq!(j.validate(), Ok(()),)
+}
+
+#[test]
+fn exclusive_conflict() {
+    let mut j: Jargon = Jargon::from_vec(vec!["exclusive_conflict", "-vty", "--out=a", "--json"]);
+    j.exclusive(vec![
+        Key::from("--json"),
+        Key::from("--out"),
+        Key::from("-y"),
     ]);
+    j.exclusive(vec!["-y", "-t"]);
 
-    if let Some(v) = j.subcommand("subsub") {
-        assert_eq!(v, vec!["subsub".to_string(), "test".to_string(),])
+    assert!(j.contains("-y"));
+    assert!(j.contains("-t"));
+    assert!(j.contains("--json"));
+    assert_eq!(j.option_arg("--out"), Some("a".to_string()),);
+    assert_eq!(
+        j.validate(),
+        Err(Error::Conflict {
+            first: "--json".to_string(),
+            second: "--out".to_string(),
+        }),
+    );
+
+    let mut j: Jargon = Jargon::from_vec(vec!["exclusive_conflict", "-ty"]);
+    j.exclusive(vec!["-y", "-t"]);
+
+    assert!(j.contains("-y"));
+    assert!(j.contains("-t"));
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::Conflict {
+            first: "-y".to_string(),
+            second: "-t".to_string(),
+        }),
+    );
+
+    let mut j: Jargon = Jargon::from_vec(vec!["exclusive_conflict", "--tab", "--json"]);
+    j.long_prefixes(vec!["--json", "--table"]);
+    j.exclusive(vec!["--json", "--table"]);
+
+    assert!(j.contains("--json"));
+    assert!(j.contains("--table"));
+    assert_eq!(
+        j.validate(),
+        Err(Error::Conflict {
+            first: "--json".to_string(),
+            second: "--tab".to_string(),
+        }),
+    )
+}
+
+#[test]
+fn exclusive_attached_value() {
+    let mut j: Jargon = Jargon::from_vec(vec!["exclusive_attached_value", "-ofile", "--json"]);
+    j.exclusive(vec!["-f", "--json"]);
+
+    assert_eq!(j.option_arg("-o"), Some("file".to_string()),);
+    assert!(!j.contains("-f"));
+    assert!(j.contains("--json"));
+    assert_eq!(j.validate(), Ok(()),);
+
+    let mut j: Jargon = Jargon::from_vec(vec!["exclusive_attached_value", "-ofile", "--json"]);
+    j.options(vec!["-o"]);
+    j.exclusive(vec!["-f", "--json"]);
+
+    assert!(!j.contains("-f"));
+    assert!(j.contains("--json"));
+    assert_eq!(j.option_arg("-o"), Some("file".to_string()),);
+    assert_eq!(j.validate(), Ok(()),)
+}
+
+#[test]
+fn exclusive_env() {
+    let mut j: Jargon = Jargon::from_vec(vec!["exclusive_env", "--json"]);
+    j.bind_env(["-f", "--format"], "APP_FORMAT");
+    j.with_env(vec![("APP_FORMAT", "yaml")]);
+    j.exclusive(vec![Key::from("--json"), Key::from(["-f", "--format"])]);
+
+    assert!(j.contains("--json"));
+    assert_eq!(j.option_arg(["-f", "--format"]), Some("yaml".to_string()),);
+    assert_eq!(
+        j.validate(),
+        Err(Error::Conflict {
+            first: "--json".to_string(),
+            second: "--format".to_string(),
+        }),
+    )
+}
+
+#[test]
+fn requires() {
+    let mut j: Jargon = Jargon::from_vec(vec!["requires", "--tls", "--tls-key", "key.pem"]);
+    j.requires("--tls-key", "--tls");
+    j.requires(["-c", "--cert"], "--tls");
+
+    assert!(j.contains("--tls"));
+    assert_eq!(j.option_arg("--tls-key"), Some("key.pem".to_string()),);
+    assert_eq!(j.validate(), Ok(()),);
+
+    let mut j: Jargon = Jargon::from_vec(vec!["requires", "--tls-key", "key.pem"]);
+    j.requires("--tls-key", "--tls");
+    j.bind_env("--tls", "APP_TLS");
+    j.with_env(vec![("APP_TLS", "1")]);
+    assert_eq!(j.option_arg("--tls-key"), Some("key.pem".to_string()),);
+    assert_eq!(j.option_arg("--tls"), Some(1),);
+    assert_eq!(j.validate(), Ok(()),)
+}
+
+#[test]
+fn requires_missing() {
+    let mut j: Jargon = Jargon::from_vec(vec!["requires_missing", "-c", "cert.pem"]);
+    j.requires(["-c", "--cert"], "--tls");
+    j.required_if("--tls-key", "--tls");
+
+    assert_eq!(j.option_arg::<String, &str>("--tls-key"), None,);
+    assert_eq!(j.option_arg(["-c", "--cert"]), Some("cert.pem".to_string()),);
+    assert!(!j.contains("--tls"));
+    assert_eq!(
+        j.validate(),
+        Err(Error::Requires {
+            key: ["-c", "--cert"].into(),
+            required: "--tls".into(),
+        }),
+    );
+
+    let mut j: Jargon = Jargon::from_vec(vec!["requires_missing", "--tls"]);
+    j.required_if("--tls-key", "--tls");
+    assert!(j.contains("--tls"));
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::Requires {
+            key: "--tls".into(),
+            required: "--tls-key".into(),
+        }),
+    )
+}
+
+#[test]
+fn requires_spec() {
+    let spec: Spec = Spec::new("srv")
+        .arg(Arg::option("--output", "FILE"))
+        .arg(Arg::flag("--stdout"))
+        .arg(Arg::option("--tls-key", "FILE"))
+        .arg(Arg::flag("--tls"))
+        .arg(Arg::option("--region", "REGION").env("APP_REGION"));
+
+    let mut j: Jargon = Jargon::from_vec(vec!["srv", "--output", "x"]);
+    j.required_unless("--output", vec!["--stdout"]);
+    j.required_unless("--region", vec!["--stdout"]);
+    j.with_env(vec![("APP_REGION", "eu")]);
+    let m = spec.parse(&mut j).unwrap();
+    assert_eq!(m.option_arg("--output"), Some("x".to_string()),);
+    assert_eq!(j.validate(), Ok(()),);
+
+    let mut j: Jargon = Jargon::from_vec(vec!["srv", "--tls-key", "key.pem"]);
+    j.requires("--tls-key", "--tls");
+    spec.parse(&mut j).unwrap();
+    assert_eq!(
+        j.finish_strict(),
+        Err(Error::Requires {
+            key: "--tls-key".into(),
+            required: "--tls".into(),
+        }),
+    )
+}
+
+#[test]
+fn required_unless() {
+    let mut j: Jargon = Jargon::from_vec(vec!["required_unless", "--dry-run"]);
+    j.required_unless("--output", vec!["--stdout", "--dry-run"]);
+    assert_eq!(j.option_arg::<String, &str>("--output"), None,);
+    assert!(!j.contains("--stdout"));
+    assert!(j.contains("--dry-run"));
+    assert_eq!(j.validate(), Ok(()),);
+
+    let mut j: Jargon = Jargon::from_vec(vec!["required_unless", "--typo"]);
+    j.required_unless("--output", vec!["--stdout", "--dry-run"]);
+    assert_eq!(
+        j.option_arg_or("--output", "out".to_string()),
+        "out".to_string(),
+    );
+    assert_eq!(
+        j.clone().finish_strict(),
+        Err(Error::UnknownArgs(vec![unknown("--typo", None)])),
+    );
+    assert_eq!(
+        j.validate(),
+        Err(Error::RequiredUnless {
+            key: "--output".into(),
+            unless: vec!["--stdout".into(), "--dry-run".into()],
+        }),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Format {
+    Json,
+    Yaml,
+    Csv,
+}
+
+impl Choices for Format {
+    fn choices() -> Vec<(&'static str, Self)> {
+        vec![
+            ("json", Format::Json),
+            ("yaml", Format::Yaml),
+            ("yml", Format::Yaml),
+            ("csv", Format::Csv),
+        ]
     }
 }
 
+fn formats() -> Vec<String> {
+    Format::spellings().into_iter().map(String::from).collect()
+}
+
 #[test]
-fn sub_none() {
-    let mut j: Jargon = Jargon::from_vec(vec!["sub_mut_none".to_string(), "test".to_string()]);
+fn choices() {
+    let mut j: Jargon = Jargon::from_vec(vec!["choices", "--format=yml", "-o", "Csv", "-t", "csv"]);
 
-    assert_eq!(j.subcommand("subsub"), None,)
+    assert_eq!(j.result_choice("--format"), Ok(Format::Yaml),);
+    assert_eq!(
+        j.result_choice::<Format, &str>("-o"),
+        Err(Error::InvalidChoice {
+            key: "-o".into(),
+            value: "Csv".to_string(),
+            choices: formats(),
+        }),
+    );
+    assert_eq!(j.option_choice("-t"), Some(Format::Csv),);
+    assert_eq!(j.option_choice::<Format, &str>("--missing"), None,);
+    assert_eq!(
+        j.result_choice::<Format, &str>("--missing"),
+        Err(Error::MissingArg("--missing".into())),
+    )
+}
+
+#[test]
+fn choices_result_arg() {
+    let mut j: Jargon =
+        Jargon::from_vec(vec!["choices_result_arg", "--color", "NEVER", "-w", "Wide"]);
+
+    assert_eq!(
+        j.result_arg_choice("--color", &["auto", "always", "never"], true),
+        Ok("never".to_string()),
+    );
+    assert_eq!(
+        j.result_arg_choice("-w", &["wide", "narrow"], false),
+        Err(Error::InvalidChoice {
+            key: "-w".into(),
+            value: "Wide".to_string(),
+            choices: vec!["wide".to_string(), "narrow".to_string()],
+        }),
+    );
+    assert_eq!(
+        j.result_arg_choice("--missing", &["a"], false),
+        Err(Error::MissingArg("--missing".into())),
+    )
+}
+
+#[test]
+fn choices_spec() {
+    let spec: Spec = Spec::new("report")
+        .arg(
+            Arg::option(["-f", "--format"], "FORMAT")
+                .help("output format")
+                .choices(Format::spellings())
+                .default("json"),
+        )
+        .arg(
+            Arg::option("--color", "WHEN")
+                .choices(vec!["auto", "always", "never"])
+                .ignore_case(),
+        );
+
+    let mut j: Jargon = Jargon::from_vec(vec!["choices_spec", "--color", "NEVER", "-f", "csv"]);
+    let m = spec.parse(&mut j).unwrap();
+    assert_eq!(m.option_arg("--color"), Some("never".to_string()),);
+    assert_eq!(m.result_choice("--format"), Ok(Format::Csv),);
+
+    let m = spec
+        .parse(&mut Jargon::from_vec(vec!["choices_spec"]))
+        .unwrap();
+    assert_eq!(m.option_choice("-f"), Some(Format::Json),);
+
+    let mut j: Jargon = Jargon::from_vec(vec!["choices_spec", "--format", "xml"]);
+    assert_eq!(
+        spec.parse(&mut j),
+        Err(Error::InvalidChoice {
+            key: ["-f", "--format"].into(),
+            value: "xml".to_string(),
+            choices: formats(),
+        }),
+    );
+
+    assert!(spec.help().contains(
+        "  -f, --format=FORMAT  output format (default: json) [possible values: json, yaml, yml, csv]\n"
+    ));
+    assert!(spec
+        .completions(Shell::Bash)
+        .contains("'--color')\n                    COMPREPLY=($(compgen -W 'auto always never' -- \"${cur}\"))"));
+    assert!(spec
+        .completions(Shell::Zsh)
+        .contains("'--color=:WHEN:(auto always never)'"));
+    assert!(spec
+        .completions(Shell::Fish)
+        .contains("complete -c 'report' -l 'color' -r -f -a 'auto always never'\n"));
 }
 
 #[test]
@@ -966,8 +2742,645 @@ fn fmt_error_missing() {
     )
 }
 
+#[test]
+fn fmt_error_missing_value() {
+    assert_eq!(
+        Error::MissingValue("--port".into()).to_string(),
+        "Missing value for argument: '--port'".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_invalid_value() {
+    let e = Error::InvalidValue {
+        key: "--port".into(),
+        value: "abc".to_string(),
+        message: "invalid digit found in string".to_string(),
+        occurrence: None,
+    };
+    assert_eq!(
+        e.to_string(),
+        "Invalid value 'abc' for argument '--port': invalid digit found in string".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_invalid_value_occurrence() {
+    let e = Error::InvalidValue {
+        key: "-n".into(),
+        value: "two".to_string(),
+        message: "invalid digit found in string".to_string(),
+        occurrence: Some(2),
+    };
+    assert_eq!(
+        e.to_string(),
+        "Invalid value 'two' for occurrence 2 of '-n': invalid digit found in string".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_invalid_choice() {
+    let e = Error::InvalidChoice {
+        key: "--format".into(),
+        value: "xml".to_string(),
+        choices: vec!["json".to_string(), "csv".to_string()],
+    };
+    assert_eq!(
+        e.to_string(),
+        "Invalid value 'xml' for argument '--format', expected one of: 'json', 'csv'".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_unknown() {
+    assert_eq!(
+        Error::UnknownArgs(vec![unknown("--verbos", Some("--verbose"))]).to_string(),
+        "Unknown argument: '--verbos'; did you mean '--verbose'?".to_string(),
+    );
+    assert_eq!(
+        Error::UnknownArgs(vec![unknown("--typo", None), unknown("-q", None)]).to_string(),
+        "Unknown arguments: '--typo', '-q'".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_missing_env() {
+    let e = Error::MissingArgEnv {
+        key: "--region".into(),
+        var: "APP_REGION".to_string(),
+    };
+    assert_eq!(
+        e.to_string(),
+        "Missing argument: '--region' or environment variable 'APP_REGION'".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_unknown_subcommand() {
+    let e = Error::UnknownSubcommand {
+        arg: Some("lsit".to_string()),
+        suggestion: Some("list".to_string()),
+        expected: vec!["list".into(), "remove".into()],
+    };
+    assert_eq!(
+        e.to_string(),
+        "Unknown subcommand: 'lsit', expected one of: 'list', 'remove'; did you mean 'list'?"
+            .to_string(),
+    );
+
+    let e = Error::UnknownSubcommand {
+        arg: Some("file.txt".to_string()),
+        suggestion: None,
+        expected: vec!["list".into()],
+    };
+    assert_eq!(
+        e.to_string(),
+        "Unknown subcommand: 'file.txt', expected one of: 'list'".to_string(),
+    );
+
+    let e = Error::UnknownSubcommand {
+        arg: None,
+        suggestion: None,
+        expected: vec!["list".into()],
+    };
+    assert_eq!(
+        e.to_string(),
+        "Missing subcommand, expected one of: 'list'".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_ambiguous() {
+    let e = Error::Ambiguous {
+        arg: "li".to_string(),
+        candidates: vec!["list".into(), "listen".into()],
+    };
+    assert_eq!(
+        e.to_string(),
+        "Ambiguous argument: 'li' could be 'list', 'listen'".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_conflict() {
+    let e = Error::Conflict {
+        first: "--json".to_string(),
+        second: "-y".to_string(),
+    };
+    assert_eq!(
+        e.to_string(),
+        "Conflicting arguments: '--json' cannot be used with '-y'".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_requires() {
+    let e = Error::Requires {
+        key: "--tls-key".into(),
+        required: "--tls".into(),
+    };
+    assert_eq!(
+        e.to_string(),
+        "Argument '--tls-key' requires '--tls'".to_string(),
+    );
+
+    let e = Error::RequiredUnless {
+        key: "--output".into(),
+        unless: vec!["--stdout".into(), "--dry-run".into()],
+    };
+    assert_eq!(
+        e.to_string(),
+        "Missing argument: '--output', required unless '--stdout' or '--dry-run' is given"
+            .to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_response_file() {
+    let e = Error::ResponseFile {
+        file: "args".to_string(),
+        line: Some(3),
+        message: "unterminated double quote".to_string(),
+    };
+    assert_eq!(
+        e.to_string(),
+        "Response file 'args', line 3: unterminated double quote".to_string(),
+    );
+
+    let e = Error::ResponseFile {
+        file: "args".to_string(),
+        line: None,
+        message: "No such file".to_string(),
+    };
+    assert_eq!(
+        e.to_string(),
+        "Response file 'args': No such file".to_string(),
+    )
+}
+
+#[test]
+fn fmt_error_config() {
+    let e = Error::Config {
+        file: "/etc/app.conf".to_string(),
+        line: Some(2),
+        message: "expected 'key = value', found 'x'".to_string(),
+    };
+    assert_eq!(
+        e.to_string(),
+        "Config file '/etc/app.conf', line 2: expected 'key = value', found 'x'".to_string(),
+    )
+}
+
 #[test]
 fn fmt_error_other() {
     let e = Error::Other(std::io::Error::new(std::io::ErrorKind::AddrInUse, "test").to_string());
     assert_eq!(e.to_string(), "test".to_string(),)
 }
+
+fn spec() -> Spec {
+    Spec::new("basename")
+        .usage("NAME [SUFFIX]")
+        .usage("OPTION... NAME...")
+        .about("Print NAME with any leading directory components removed.")
+        .arg(Arg::flag(["-a", "--multiple"]).help("support multiple arguments"))
+        .arg(Arg::option(["-s", "--suffix"], "SUFFIX").help("remove a trailing SUFFIX"))
+        .arg(Arg::option("-n", "N").help("print N names").default(1))
+        .arg(Arg::flag("--zero").help("end each output line with NUL"))
+        .arg(Arg::flag(["-v", "--verbose"]))
+}
+
+#[test]
+fn spec_help() {
+    assert_eq!(
+        spec().help(),
+        "Usage: basename NAME [SUFFIX]
+  or:  basename OPTION... NAME...
+Print NAME with any leading directory components removed.
+
+  -a, --multiple       support multiple arguments
+  -s, --suffix=SUFFIX  remove a trailing SUFFIX
+  -n N                 print N names (default: 1)
+      --zero           end each output line with NUL
+  -v, --verbose
+",
+    )
+}
+
+#[test]
+fn spec_help_default_usage() {
+    assert_eq!(
+        Spec::new("true").help(),
+        "Usage: true [OPTION]...\n".to_string(),
+    )
+}
+
+#[test]
+fn spec_parse() {
+    let mut j = Jargon::from_vec(vec![
+        "spec_parse",
+        "-vas",
+        ".txt",
+        "a.txt",
+        "--verbose",
+        "--other",
+    ]);
+    let m = spec().parse(&mut j).unwrap();
+
+    assert!(m.contains("-a"));
+    assert!(!m.contains("--zero"));
+    assert_eq!(m.count(["-v", "--verbose"]), 2,);
+    assert_eq!(m.option_arg("--suffix"), Some(".txt".to_string()),);
+    assert_eq!(m.result_arg("-n"), Ok(1u8),);
+    assert_eq!(j.finish(), vec!["a.txt".to_string(), "--other".to_string()],)
+}
+
+#[test]
+fn spec_parse_option_order() {
+    let mut j = Jargon::from_vec(vec!["spec_parse_option_order", "-svalue", "-n5", "-a"]);
+    let m = spec().parse(&mut j).unwrap();
+
+    assert!(m.contains("--multiple"));
+    assert!(!m.contains("-v"));
+    assert_eq!(m.option_arg("-s"), Some("value".to_string()),);
+    assert_eq!(m.option_args::<u8, &str>("-n"), vec![5],);
+    assert!(j.finish().is_empty())
+}
+
+#[test]
+fn spec_parse_missing_value() {
+    let mut j = Jargon::from_vec(vec!["spec_parse_missing_value", "--suffix"]);
+
+    assert_eq!(
+        spec().parse(&mut j),
+        Err(Error::MissingValue(["-s", "--suffix"].into())),
+    )
+}
+
+#[test]
+fn spec_result_arg_missing() {
+    let mut j = Jargon::from_vec(vec!["spec_result_arg_missing"]);
+    let m = spec().parse(&mut j).unwrap();
+
+    assert_eq!(
+        m.result_arg::<String, &str>("--suffix"),
+        Err(Error::MissingArg("--suffix".into())),
+    )
+}
+
+#[test]
+fn spec_result_args_invalid() {
+    let mut j = Jargon::from_vec(vec!["spec_result_args_invalid", "-n", "1", "-n", "two"]);
+    let m = spec().parse(&mut j).unwrap();
+
+    assert_eq!(
+        m.result_args::<u8, &str>("-n"),
+        Err(Error::InvalidValue {
+            key: "-n".into(),
+            value: "two".to_string(),
+            message: "invalid digit found in string".to_string(),
+            occurrence: Some(2),
+        }),
+    )
+}
+
+fn nested() -> Spec {
+    Spec::new("git-ish")
+        .arg(Arg::flag(["-v", "--verbose"]).help("say more"))
+        .subcommand(
+            Spec::new("remote")
+                .about("Manage remotes\nin detail")
+                .arg(Arg::option("--name", "NAME").help("the remote's name"))
+                .subcommand(Spec::new("add").arg(Arg::flag("-f"))),
+        )
+        .subcommand(Spec::new("status"))
+}
+
+#[test]
+fn spec_help_subcommands() {
+    assert_eq!(
+        nested().help(),
+        "Usage: git-ish [OPTION]...
+
+  -v, --verbose  say more
+
+Subcommands:
+  remote  Manage remotes
+  status
+",
+    )
+}
+
+#[test]
+fn spec_parse_unknown_subcommand() {
+    let mut j = Jargon::from_vec(vec!["spec_parse_unknown_subcommand", "remtoe"]);
+    nested().parse(&mut j).unwrap();
+
+    assert_eq!(
+        j.dispatch()
+            .on("remote", |_| Ok(()))
+            .on("status", |_| Ok(()))
+            .run(),
+        Err(Error::UnknownSubcommand {
+            arg: Some("remtoe".to_string()),
+            suggestion: Some("remote".to_string()),
+            expected: vec!["remote".into(), "status".into()],
+        }),
+    )
+}
+
+#[test]
+fn shell_from_str() {
+    assert_eq!("zsh".parse(), Ok(Shell::Zsh),);
+    assert_eq!(
+        "csh".parse::<Shell>(),
+        Err(Error::Other(
+            "Unknown shell: 'csh', expected 'bash', 'zsh' or 'fish'".to_string()
+        )),
+    )
+}
+
+#[test]
+fn completions_bash() {
+    let script: String = nested().completions(Shell::Bash);
+
+    assert!(script.contains("            'git_ish__remote,add') cmd=git_ish__remote__add ;;\n"));
+    assert!(script.contains("                '--name')\n"));
+    assert!(script.contains("compgen -W '-v --verbose remote status' -- \"${cur}\""));
+    assert!(script.ends_with("complete -o default -F _git_ish 'git-ish'\n"))
+}
+
+#[test]
+fn completions_zsh() {
+    let script: String = nested().completions(Shell::Zsh);
+
+    assert!(script.starts_with("#compdef git-ish\n"));
+    assert!(script.contains("'(-v --verbose)'{-v,--verbose}'[say more]'"));
+    assert!(script.contains("'--name=[the remote'\\''s name]:NAME:_files'"));
+    assert!(script.contains("                'remote:Manage remotes'\n                'status'\n"));
+    assert!(script.contains("                'add') _git_ish__remote__add ;;\n"))
+}
+
+#[test]
+fn completions_fish() {
+    let script: String = nested().completions(Shell::Fish);
+
+    assert!(script.contains(
+        "complete -c 'git-ish' -n 'test (__fish_git_ish_command) = git_ish' -f -a 'remote' -d 'Manage remotes'\n"
+    ));
+    assert!(script.contains(
+        "complete -c 'git-ish' -n 'test (__fish_git_ish_command) = git_ish__remote' -l 'name' -r -d 'the remote\\'s name'\n"
+    ));
+    assert!(script.contains(
+        "complete -c 'git-ish' -n 'test (__fish_git_ish_command) = git_ish__remote__add' -s f\n"
+    ))
+}
+
+#[test]
+fn spec_man() {
+    assert_eq!(
+        spec().man(),
+        ".TH BASENAME 1
+.SH NAME
+basename \\- Print NAME with any leading directory components removed.
+.SH SYNOPSIS
+\\fBbasename\\fR NAME [SUFFIX]
+.br
+\\fBbasename\\fR OPTION... NAME...
+.SH DESCRIPTION
+Print NAME with any leading directory components removed.
+.SH OPTIONS
+.TP
+\\fB\\-a\\fR, \\fB\\-\\-multiple\\fR
+support multiple arguments
+.TP
+\\fB\\-s\\fR, \\fB\\-\\-suffix\\fR=\\fISUFFIX\\fR
+remove a trailing SUFFIX
+.TP
+\\fB\\-n\\fR \\fIN\\fR
+print N names (default: 1)
+.TP
+\\fB\\-\\-zero\\fR
+end each output line with NUL
+.TP
+\\fB\\-v\\fR, \\fB\\-\\-verbose\\fR
+",
+    )
+}
+
+#[test]
+fn spec_man_subcommands() {
+    assert_eq!(
+        nested().man(),
+        ".TH GIT\\-ISH 1
+.SH NAME
+git\\-ish
+.SH SYNOPSIS
+\\fBgit\\-ish\\fR [OPTION]...
+.SH OPTIONS
+.TP
+\\fB\\-v\\fR, \\fB\\-\\-verbose\\fR
+say more
+.SH SUBCOMMANDS
+.TP
+\\fBremote\\fR
+Manage remotes
+.RS
+.TP
+\\fB\\-\\-name\\fR=\\fINAME\\fR
+the remote's name
+.RE
+.TP
+\\fBremote add\\fR
+.RS
+.TP
+\\fB\\-f\\fR
+.RE
+.TP
+\\fBstatus\\fR
+",
+    )
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use crate::{Error, FromJargon, Jargon, Key, Unknown};
+
+    /// Copy SOURCE to DEST.
+    #[derive(Debug, PartialEq, crate::Jargon)]
+    #[jargon(name = "cp")]
+    struct Copy {
+        /// explain what is being done
+        #[jargon(short, long, count)]
+        verbose: u8,
+        #[jargon(short = 'r', long)]
+        recursive: bool,
+        /// make a backup of each existing destination file
+        #[jargon(long, value = "CONTROL", default = "existing", env = "VERSION_CONTROL")]
+        backup: String,
+        #[jargon(short = 'S', long)]
+        suffix: Option<String>,
+        #[jargon(long, value = "ATTR")]
+        preserve: Vec<String>,
+        #[jargon(long)]
+        jobs: Option<u8>,
+        #[jargon(positional)]
+        source: String,
+        #[jargon(positional)]
+        dest: Vec<std::path::PathBuf>,
+    }
+
+    #[derive(Debug, PartialEq, crate::Jargon)]
+    #[jargon(name = "true")]
+    struct Nothing {
+        #[jargon(short)]
+        quiet: bool,
+    }
+
+    #[derive(Debug, PartialEq, crate::Jargon)]
+    #[jargon(name = "cat")]
+    struct Help {
+        /// display this help and exit
+        #[jargon(short, long, help)]
+        help: bool,
+        #[jargon(short, long)]
+        number: bool,
+        #[jargon(positional)]
+        file: String,
+    }
+
+    #[test]
+    fn derive_parse() {
+        let j = Jargon::from_vec(vec![
+            "cp",
+            "-vv",
+            "--preserve=mode",
+            "a",
+            "-r",
+            "--preserve",
+            "links",
+            "b",
+            "--verbose",
+        ]);
+
+        assert_eq!(
+            Copy::from_jargon(j),
+            Ok(Copy {
+                verbose: 3,
+                recursive: true,
+                backup: "existing".to_string(),
+                suffix: None,
+                preserve: vec!["mode".to_string(), "links".to_string()],
+                jobs: None,
+                source: "a".to_string(),
+                dest: vec!["b".into()],
+            }),
+        )
+    }
+
+    #[test]
+    fn derive_parse_env() {
+        let mut j = Jargon::from_vec(vec!["cp", "-S", "~", "--jobs=4", "a"]);
+        j.with_env(vec![("VERSION_CONTROL", "numbered")]);
+        let copy = Copy::from_jargon(j).unwrap();
+
+        assert_eq!(copy.backup, "numbered".to_string(),);
+        assert_eq!(copy.suffix, Some("~".to_string()),);
+        assert_eq!(copy.jobs, Some(4),);
+        assert!(copy.dest.is_empty())
+    }
+
+    #[test]
+    fn derive_parse_invalid() {
+        let j = Jargon::from_vec(vec!["cp", "--jobs", "many", "a"]);
+
+        assert_eq!(
+            Copy::from_jargon(j),
+            Err(Error::InvalidValue {
+                key: "--jobs".into(),
+                value: "many".to_string(),
+                message: "invalid digit found in string".to_string(),
+                occurrence: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn derive_parse_missing_positional() {
+        let j = Jargon::from_vec(vec!["cp", "-r"]);
+
+        assert_eq!(
+            Copy::from_jargon(j),
+            Err(Error::MissingArg(Key::from("SOURCE"))),
+        )
+    }
+
+    #[test]
+    fn derive_parse_help() {
+        let j = Jargon::from_vec(vec!["cat", "--help"]);
+
+        assert_eq!(Help::from_jargon(j), Err(Error::Help(Help::help())),);
+
+        let j = Jargon::from_vec(vec!["cat", "-nh", "a", "--bogus"]);
+
+        assert_eq!(Help::from_jargon(j), Err(Error::Help(Help::help())),);
+
+        let j = Jargon::from_vec(vec!["cat", "-n", "a"]);
+
+        assert_eq!(
+            Help::from_jargon(j),
+            Ok(Help {
+                help: false,
+                number: true,
+                file: "a".to_string(),
+            }),
+        );
+        assert_eq!(
+            Help::help(),
+            "Usage: cat [OPTION]... FILE
+
+  -h, --help    display this help and exit
+  -n, --number
+",
+        )
+    }
+
+    #[test]
+    fn derive_parse_unknown() {
+        let j = Jargon::from_vec(vec!["true", "-q", "extra"]);
+
+        assert_eq!(
+            Nothing::from_jargon(j),
+            Err(Error::UnknownArgs(vec![Unknown {
+                arg: "extra".to_string(),
+                suggestion: None,
+            }])),
+        );
+
+        let j = Jargon::from_vec(vec!["cp", "--recursiv", "a"]);
+
+        assert_eq!(
+            Copy::from_jargon(j),
+            Err(Error::UnknownArgs(vec![Unknown {
+                arg: "--recursiv".to_string(),
+                suggestion: Some("--recursive".to_string()),
+            }])),
+        )
+    }
+
+    #[test]
+    fn derive_help() {
+        assert_eq!(
+            Copy::help(),
+            "Usage: cp [OPTION]... SOURCE [DEST]...
+Copy SOURCE to DEST.
+
+  -v, --verbose         explain what is being done
+  -r, --recursive
+      --backup=CONTROL  make a backup of each existing destination file (default: existing) [env: VERSION_CONTROL]
+  -S, --suffix=SUFFIX
+      --preserve=ATTR
+      --jobs=JOBS
+",
+        )
+    }
+}